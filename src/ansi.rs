@@ -0,0 +1,126 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::SmartString;
+
+pub(crate) type Rgb = (u8, u8, u8);
+
+const PALETTE: [Rgb; 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Text attributes set by ANSI SGR color codes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Style {
+    pub(crate) foreground: Option<Rgb>,
+    pub(crate) background: Option<Rgb>,
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+    pub(crate) underline: bool,
+}
+
+impl Style {
+    // Applies an SGR sequence such as `\x1b[1;34m`. Other escape sequences
+    // are ignored.
+    fn apply(&mut self, code: &str) {
+        let params = match code
+            .strip_prefix("\u{1b}[")
+            .and_then(|c| c.strip_suffix('m'))
+        {
+            Some(params) => params,
+            None => return,
+        };
+        let mut params = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => self.bold = false,
+                23 => self.italic = false,
+                24 => self.underline = false,
+                30..=37 => self.foreground = Some(PALETTE[usize::from(param - 30)]),
+                38 => self.foreground = extended_color(&mut params),
+                39 => self.foreground = None,
+                40..=47 => self.background = Some(PALETTE[usize::from(param - 40)]),
+                48 => self.background = extended_color(&mut params),
+                49 => self.background = None,
+                90..=97 => self.foreground = Some(PALETTE[usize::from(param - 90 + 8)]),
+                100..=107 => self.background = Some(PALETTE[usize::from(param - 100 + 8)]),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn extended_color(params: &mut dyn Iterator<Item = u8>) -> Option<Rgb> {
+    match params.next()? {
+        5 => Some(indexed_color(params.next()?)),
+        2 => Some((params.next()?, params.next()?, params.next()?)),
+        _ => None,
+    }
+}
+
+fn indexed_color(index: u8) -> Rgb {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match index {
+        0..=15 => PALETTE[usize::from(index)],
+        16..=231 => {
+            let index = usize::from(index - 16);
+            (LEVELS[index / 36], LEVELS[index / 6 % 6], LEVELS[index % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// Formats a color as a `#rrggbb` hex string.
+pub(crate) fn hex((red, green, blue): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+/// Splits the visible text into runs that share the same style.
+pub(crate) fn runs(text: &SmartString) -> Vec<(Style, &str)> {
+    let mut style = Style::default();
+    let mut result = Vec::new();
+    for (visible, string) in &text.fragments {
+        if *visible {
+            result.push((style, string.as_str()));
+        } else {
+            style.apply(string);
+        }
+    }
+    result
+}
@@ -0,0 +1,83 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Align, AsciiTable, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_asciidoc(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        let cols: Vec<_> = (0..num_cols)
+            .map(|a| match self.column_align(a) {
+                Align::Left => "<",
+                Align::Center => "^",
+                Align::Right => ">",
+            })
+            .collect();
+        let header = self.stringify_header(num_cols);
+        let has_header = header.iter().any(|text| !text.is_empty());
+        let footer = self.stringify_footer(num_cols);
+        let mut options = Vec::new();
+        if has_header {
+            options.push("header");
+        }
+        // AsciiDoc only marks the last row as the footer, so earlier footer
+        // rows stay in the body.
+        if !footer.is_empty() {
+            options.push("footer");
+        }
+        let options = if options.is_empty() {
+            String::new()
+        } else {
+            format!(",options=\"{}\"", options.join(","))
+        };
+        writeln!(out, "[cols=\"{}\"{}]", cols.join(","), options)?;
+        writeln!(out, "|===")?;
+        if has_header {
+            write_row(out, &header)?;
+        }
+        for row in rows.chain(footer) {
+            write_row(out, &row)?;
+        }
+        writeln!(out, "|===")
+    }
+}
+
+fn write_row(out: &mut dyn fmt::Write, row: &[SmartString]) -> fmt::Result {
+    let cells: Vec<_> = row
+        .iter()
+        .map(|text| format!("|{}", escape(text)))
+        .collect();
+    writeln!(out, "{}", cells.join(" "))
+}
+
+// Line breaks inside a cell are kept with the ` +` hard break.
+fn escape(text: &SmartString) -> String {
+    let lines: Vec<_> = text
+        .lines()
+        .iter()
+        .map(|line| line.plain().replace('|', "\\|"))
+        .collect();
+    lines.join(" +\n")
+}
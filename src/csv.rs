@@ -0,0 +1,61 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{AsciiTable, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_csv(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+        delimiter: char,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        let header = self.stringify_header(num_cols);
+        if header.iter().any(|text| !text.is_empty()) {
+            write_record(out, &header, delimiter)?;
+        }
+        for row in rows.chain(self.stringify_footer(num_cols)) {
+            write_record(out, &row, delimiter)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_record(out: &mut dyn fmt::Write, row: &[SmartString], delimiter: char) -> fmt::Result {
+    for (a, text) in row.iter().enumerate() {
+        if a > 0 {
+            out.write_char(delimiter)?;
+        }
+        out.write_str(&quote(&text.plain(), delimiter))?;
+    }
+    writeln!(out)
+}
+
+// Fields containing the delimiter, quotes or line breaks are quoted as in
+// RFC 4180, with quotes doubled.
+fn quote(text: &str, delimiter: char) -> String {
+    if text.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::ansi::{self, Style};
+use crate::{Align, AsciiTable, Cell, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_html(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        writeln!(out, "<table>")?;
+        let header = self.stringify_header(num_cols);
+        if header.iter().any(|text| !text.is_empty()) {
+            writeln!(out, "<thead>")?;
+            write_row(out, "th", &self.header_row(header))?;
+            writeln!(out, "</thead>")?;
+        }
+        writeln!(out, "<tbody>")?;
+        for row in rows {
+            let row: Vec<_> = row
+                .into_iter()
+                .enumerate()
+                .map(|(column, text)| (text, self.column_align(column)))
+                .collect();
+            write_cells(out, "td", row.iter().map(|(text, align)| (text, *align)))?;
+        }
+        writeln!(out, "</tbody>")?;
+        let footer = self.stringify_footer(num_cols);
+        if !footer.is_empty() {
+            writeln!(out, "<tfoot>")?;
+            for row in footer {
+                write_row(out, "td", &self.footer_row(row))?;
+            }
+            writeln!(out, "</tfoot>")?;
+        }
+        writeln!(out, "</table>")
+    }
+}
+
+fn write_row(out: &mut dyn fmt::Write, tag: &str, row: &[Cell]) -> fmt::Result {
+    write_cells(out, tag, row.iter().map(|cell| (&cell.text, cell.align)))
+}
+
+fn write_cells<'a>(
+    out: &mut dyn fmt::Write,
+    tag: &str,
+    cells: impl Iterator<Item = (&'a SmartString, Align)>,
+) -> fmt::Result {
+    write!(out, "<tr>")?;
+    for (text, align) in cells {
+        let align = match align {
+            Align::Left => "left",
+            Align::Center => "center",
+            Align::Right => "right",
+        };
+        write!(out, "<{} style=\"text-align: {}\">", tag, align)?;
+        for (a, line) in text.lines().iter().enumerate() {
+            if a > 0 {
+                write!(out, "<br>")?;
+            }
+            for (style, text) in ansi::runs(line) {
+                write_run(out, style, text)?;
+            }
+        }
+        write!(out, "</{}>", tag)?;
+    }
+    writeln!(out, "</tr>")
+}
+
+// Text with color codes is wrapped in a `<span>` carrying the same style.
+fn write_run(out: &mut dyn fmt::Write, style: Style, text: &str) -> fmt::Result {
+    if style == Style::default() {
+        return write!(out, "{}", escape(text));
+    }
+    let mut css = Vec::new();
+    if let Some(color) = style.foreground {
+        css.push(format!("color: {}", ansi::hex(color)));
+    }
+    if let Some(color) = style.background {
+        css.push(format!("background-color: {}", ansi::hex(color)));
+    }
+    if style.bold {
+        css.push("font-weight: bold".to_string());
+    }
+    if style.italic {
+        css.push("font-style: italic".to_string());
+    }
+    if style.underline {
+        css.push("text-decoration: underline".to_string());
+    }
+    write!(
+        out,
+        "<span style=\"{}\">{}</span>",
+        css.join("; "),
+        escape(text)
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
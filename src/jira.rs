@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{AsciiTable, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_jira(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        let header = self.stringify_header(num_cols);
+        if header.iter().any(|text| !text.is_empty()) {
+            write_row(out, "||", &header)?;
+        }
+        for row in rows.chain(self.stringify_footer(num_cols)) {
+            write_row(out, "|", &row)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_row(out: &mut dyn fmt::Write, marker: &str, row: &[SmartString]) -> fmt::Result {
+    let cells: Vec<_> = row.iter().map(escape).collect();
+    writeln!(out, "{}{}{}", marker, cells.join(marker), marker)
+}
+
+// An empty cell would merge its markers into `||`, so it is written as a
+// single space. Line breaks become the `\\` forced break, which is why a
+// backslash in the text is written as the `&#92;` entity instead.
+fn escape(text: &SmartString) -> String {
+    let lines: Vec<_> = text
+        .lines()
+        .iter()
+        .map(|line| {
+            let mut result = String::new();
+            for ch in line.plain().chars() {
+                match ch {
+                    '\\' => result.push_str("&#92;"),
+                    '|' | '{' | '}' | '[' | ']' => {
+                        result.push('\\');
+                        result.push(ch);
+                    }
+                    ch => result.push(ch),
+                }
+            }
+            result
+        })
+        .collect();
+    match lines.join("\\\\") {
+        text if text.is_empty() => " ".to_string(),
+        text => text,
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{AsciiTable, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_json(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+        lines: bool,
+    ) -> fmt::Result {
+        let mut keys: Vec<String> = Vec::with_capacity(num_cols);
+        for (a, header) in self.stringify_header(num_cols).iter().enumerate() {
+            let key = if header.is_empty() {
+                format!("col{}", a)
+            } else {
+                header.plain()
+            };
+            // Repeated keys get a numbered suffix, as JSON objects need unique keys.
+            let mut unique = key.clone();
+            let mut count = 1;
+            while keys.contains(&unique) {
+                count += 1;
+                unique = format!("{}_{}", key, count);
+            }
+            keys.push(unique);
+        }
+        if !lines {
+            write!(out, "[")?;
+        }
+        let mut count = 0;
+        for row in rows.chain(self.stringify_footer(num_cols)) {
+            if !lines {
+                out.write_str(if count == 0 { "\n  " } else { ",\n  " })?;
+            }
+            write_object(out, &keys, &row)?;
+            if lines {
+                writeln!(out)?;
+            }
+            count += 1;
+        }
+        if lines {
+            Ok(())
+        } else if count == 0 {
+            writeln!(out, "]")
+        } else {
+            writeln!(out, "\n]")
+        }
+    }
+}
+
+fn write_object(out: &mut dyn fmt::Write, keys: &[String], row: &[SmartString]) -> fmt::Result {
+    write!(out, "{{")?;
+    for (a, (key, text)) in keys.iter().zip(row).enumerate() {
+        if a > 0 {
+            write!(out, ", ")?;
+        }
+        write!(out, "{}: {}", quote(key), quote(&text.plain()))?;
+    }
+    write!(out, "}}")
+}
+
+fn quote(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\u{8}' => result.push_str("\\b"),
+            '\u{c}' => result.push_str("\\f"),
+            ch if ch < ' ' => result.push_str(&format!("\\u{:04x}", u32::from(ch))),
+            ch => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
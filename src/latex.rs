@@ -0,0 +1,115 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Align, AsciiTable, Cell, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_latex(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+        booktabs: bool,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        let (top, mid, bottom) = if booktabs {
+            ("\\toprule", "\\midrule", "\\bottomrule")
+        } else {
+            ("\\hline", "\\hline", "\\hline")
+        };
+        let aligns: Vec<_> = (0..num_cols).map(|a| self.column_align(a)).collect();
+        let spec: String = aligns.iter().map(|&align| align.letter()).collect();
+        writeln!(out, "\\begin{{tabular}}{{{}}}", spec)?;
+        writeln!(out, "{}", top)?;
+        let header = self.stringify_header(num_cols);
+        if header.iter().any(|text| !text.is_empty()) {
+            write_labels(out, &self.header_row(header), &aligns)?;
+            writeln!(out, "{}", mid)?;
+        }
+        for row in rows {
+            let cells: Vec<_> = row
+                .iter()
+                .zip(&aligns)
+                .map(|(text, &align)| escape(text, align))
+                .collect();
+            writeln!(out, "{} \\\\", cells.join(" & "))?;
+        }
+        let footer = self.stringify_footer(num_cols);
+        if !footer.is_empty() {
+            writeln!(out, "{}", mid)?;
+            for row in footer {
+                write_labels(out, &self.footer_row(row), &aligns)?;
+            }
+        }
+        writeln!(out, "{}", bottom)?;
+        writeln!(out, "\\end{{tabular}}")
+    }
+}
+
+// Header and footer cells aligned differently from their column are wrapped
+// in `\multicolumn`.
+fn write_labels(out: &mut dyn fmt::Write, row: &[Cell], aligns: &[Align]) -> fmt::Result {
+    let cells: Vec<_> = row
+        .iter()
+        .zip(aligns)
+        .map(|(cell, &align)| {
+            if cell.align == align {
+                escape(&cell.text, align)
+            } else {
+                format!(
+                    "\\multicolumn{{1}}{{{}}}{{{}}}",
+                    cell.align.letter(),
+                    escape(&cell.text, cell.align)
+                )
+            }
+        })
+        .collect();
+    writeln!(out, "{} \\\\", cells.join(" & "))
+}
+
+// Multi-line cells are stacked with `\shortstack`.
+fn escape(text: &SmartString, align: Align) -> String {
+    let lines: Vec<_> = text
+        .lines()
+        .iter()
+        .map(|line| escape_line(&line.plain()))
+        .collect();
+    match lines.len() {
+        1 => lines.concat(),
+        _ => format!("\\shortstack[{}]{{{}}}", align.letter(), lines.join("\\\\")),
+    }
+}
+
+fn escape_line(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            '\\' => result.push_str("\\textbackslash{}"),
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
@@ -62,42 +62,96 @@
 //! ];
 //! ascii_table.print(data);
 //! // ┌─────┬─────┬─────┬──────┐
-//! // │ H1  │ H2  │ H3  │      │
+//! // │ H1  │ H2  │  H3 │      │
 //! // ├─────┼─────┼─────┼──────┤
 //! // │ v   │  v  │   v │      │
 //! // │ 123 │ 456 │ 789 │ abc+ │
 //! // └─────┴─────┴─────┴──────┘
 //! ```
 
+mod ansi;
+mod asciidoc;
+mod csv;
+mod html;
+mod jira;
+mod json;
+mod latex;
+mod markdown;
+mod mediawiki;
+mod org;
+#[cfg(feature = "serde")]
+mod records;
+mod roff;
+mod rst;
+mod svg;
 #[cfg(test)]
 mod test;
 
+#[cfg(feature = "serde")]
+pub use records::SerializeError;
+
+use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::fmt::Display;
-use unicode_width::UnicodeWidthStr;
-
-const SE: &str = "┌";
-const NW: &str = "┘";
-const SW: &str = "┐";
-const NS: &str = "│";
-const NE: &str = "└";
-const EWS: &str = "┬";
-const NES: &str = "├";
-const NWS: &str = "┤";
-const NEW: &str = "┴";
-const NEWS: &str = "┼";
-const EW: &str = "─";
+use std::env;
+use std::fmt::{self, Display};
+use std::io::{self, IsTerminal};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
 const DEFAULT_ALIGN: Align = Align::Left;
+const DEFAULT_VERTICAL_ALIGN: VerticalAlign = VerticalAlign::Top;
+const DEFAULT_OVERFLOW: Overflow = Overflow::Truncate;
+const DEFAULT_TRUNCATION: Truncation = Truncation::End;
 const DEFAULT_COLUMN: Column = Column {
     header: String::new(),
     align: DEFAULT_ALIGN,
-    max_width: usize::max_value(),
+    vertical_align: DEFAULT_VERTICAL_ALIGN,
+    header_align: None,
+    footer_align: None,
+    max_width: usize::MAX,
+    width: None,
+    overflow: DEFAULT_OVERFLOW,
+    truncation: DEFAULT_TRUNCATION,
+    truncation_marker: Cow::Borrowed("+"),
+    padding_left: None,
+    padding_right: None,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AsciiTable {
     pub max_width: usize,
+    /// Use the width of the terminal instead of `max_width` when stdout is a terminal.
+    /// `max_width` remains the fallback for other outputs.
+    pub use_terminal_width: bool,
     pub default_align: Align,
+    pub header_align: Option<Align>,
+    pub border: BorderStyle,
+    pub padding_left: usize,
+    pub padding_right: usize,
+    pub indent: usize,
+    pub separate_rows: bool,
+    /// Rules drawn after the data rows with the given indices, overriding `separate_rows`.
+    /// The junctions come from the rule's style, so a heavier rule in a `THIN` table should use
+    /// `BorderStyle::HEAVY_SEPARATOR` or `BorderStyle::DOUBLE_SEPARATOR`.
+    pub separators: BTreeMap<usize, BorderStyle>,
+    /// Number of columns spanned by the data cell at `(row, column)`. The cells it covers are not
+    /// shown.
+    pub col_spans: BTreeMap<(usize, usize), usize>,
+    /// Number of data rows spanned by the cell at `(row, column)`. The cells it covers are not
+    /// shown.
+    pub row_spans: BTreeMap<(usize, usize), usize>,
+    /// Headers drawn above the column headers, each spanning columns from its key onwards.
+    pub column_groups: BTreeMap<usize, ColumnGroup>,
+    pub footer: Vec<Vec<String>>,
+    pub footer_align: Option<Align>,
+    pub footer_separator: Option<BorderStyle>,
+    pub title: String,
+    pub title_align: Align,
+    pub title_position: TitlePosition,
+    pub caption: String,
+    pub caption_align: Align,
+    /// Format the table is written in. Formats other than `Output::Table` ignore the layout
+    /// settings, such as widths, borders, spans and titles.
+    pub output: Output,
     pub columns: BTreeMap<usize, Column>,
 }
 
@@ -105,7 +159,27 @@ impl Default for AsciiTable {
     fn default() -> Self {
         Self {
             max_width: 80,
+            use_terminal_width: false,
             default_align: Align::Left,
+            header_align: None,
+            border: BorderStyle::THIN,
+            padding_left: 1,
+            padding_right: 1,
+            indent: 0,
+            separate_rows: false,
+            separators: BTreeMap::new(),
+            col_spans: BTreeMap::new(),
+            row_spans: BTreeMap::new(),
+            column_groups: BTreeMap::new(),
+            footer: Vec::new(),
+            footer_align: None,
+            footer_separator: None,
+            title: String::new(),
+            title_align: Align::Left,
+            title_position: TitlePosition::Border,
+            caption: String::new(),
+            caption_align: Align::Left,
+            output: Output::Table,
             columns: BTreeMap::new(),
         }
     }
@@ -115,14 +189,26 @@ impl Default for AsciiTable {
 pub struct Column {
     pub header: String,
     pub align: Align,
+    pub vertical_align: VerticalAlign,
+    pub header_align: Option<Align>,
+    pub footer_align: Option<Align>,
     pub max_width: usize,
+    /// Fixed width of the column content, used instead of the width measured from the data.
+    pub width: Option<usize>,
+    pub overflow: Overflow,
+    pub truncation: Truncation,
+    /// Text put in place of the removed content, either a literal or a `String`.
+    pub truncation_marker: Cow<'static, str>,
+    pub padding_left: Option<usize>,
+    pub padding_right: Option<usize>,
 }
 
 impl Column {
     pub fn with_header(header: &str) -> Self {
-        let mut result = Self::default();
-        result.header = header.into();
-        result
+        Self {
+            header: header.into(),
+            ..Self::default()
+        }
     }
 }
 
@@ -132,6 +218,23 @@ impl Default for Column {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnGroup {
+    pub header: String,
+    pub span: usize,
+    pub align: Align,
+}
+
+impl ColumnGroup {
+    pub fn new(header: &str, span: usize) -> Self {
+        Self {
+            header: header.into(),
+            span,
+            align: Align::Center,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Align {
     Left,
@@ -139,28 +242,287 @@ pub enum Align {
     Right,
 }
 
+impl Align {
+    // Initial of the alignment, as used by LaTeX column specs, tbl formats and
+    // Org mode cookies.
+    pub(crate) fn letter(self) -> char {
+        match self {
+            Align::Left => 'l',
+            Align::Center => 'c',
+            Align::Right => 'r',
+        }
+    }
+}
+
 impl Default for Align {
     fn default() -> Self {
         DEFAULT_ALIGN
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum VerticalAlign {
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl Default for VerticalAlign {
+    fn default() -> Self {
+        DEFAULT_VERTICAL_ALIGN
+    }
+}
+
+/// What to do with text that is wider than its column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Overflow {
+    /// Cut the text and mark the cut.
+    Truncate,
+    /// Continue the text on the next line, breaking anywhere.
+    Wrap,
+    /// Continue the text on the next line, breaking on whitespace where possible.
+    WrapWords,
+}
+
+impl Default for Overflow {
+    fn default() -> Self {
+        DEFAULT_OVERFLOW
+    }
+}
+
+/// Which part of the text to cut when it is truncated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Truncation {
+    End,
+    Start,
+    Middle,
+}
+
+impl Default for Truncation {
+    fn default() -> Self {
+        DEFAULT_TRUNCATION
+    }
+}
+
+/// The glyphs used to draw the border and the lines between cells.
+///
+/// Every glyph except `horizontal` is expected to be as wide as `vertical`.
+/// An empty `horizontal` leaves out the horizontal lines altogether.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct BorderStyle {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub top_junction: &'static str,
+    pub bottom_junction: &'static str,
+    pub left_junction: &'static str,
+    pub right_junction: &'static str,
+    pub cross: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+}
+
+impl BorderStyle {
+    pub const THIN: BorderStyle = BorderStyle {
+        top_left: "┌",
+        top_right: "┐",
+        bottom_left: "└",
+        bottom_right: "┘",
+        top_junction: "┬",
+        bottom_junction: "┴",
+        left_junction: "├",
+        right_junction: "┤",
+        cross: "┼",
+        horizontal: "─",
+        vertical: "│",
+    };
+
+    pub const ASCII: BorderStyle = BorderStyle {
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        top_junction: "+",
+        bottom_junction: "+",
+        left_junction: "+",
+        right_junction: "+",
+        cross: "+",
+        horizontal: "-",
+        vertical: "|",
+    };
+
+    pub const DOUBLE: BorderStyle = BorderStyle {
+        top_left: "╔",
+        top_right: "╗",
+        bottom_left: "╚",
+        bottom_right: "╝",
+        top_junction: "╦",
+        bottom_junction: "╩",
+        left_junction: "╠",
+        right_junction: "╣",
+        cross: "╬",
+        horizontal: "═",
+        vertical: "║",
+    };
+
+    pub const HEAVY: BorderStyle = BorderStyle {
+        top_left: "┏",
+        top_right: "┓",
+        bottom_left: "┗",
+        bottom_right: "┛",
+        top_junction: "┳",
+        bottom_junction: "┻",
+        left_junction: "┣",
+        right_junction: "┫",
+        cross: "╋",
+        horizontal: "━",
+        vertical: "┃",
+    };
+
+    /// Heavy horizontal lines joining thin vertical ones, for separators in a `THIN` table.
+    pub const HEAVY_SEPARATOR: BorderStyle = BorderStyle {
+        top_left: "┍",
+        top_right: "┑",
+        bottom_left: "┕",
+        bottom_right: "┙",
+        top_junction: "┯",
+        bottom_junction: "┷",
+        left_junction: "┝",
+        right_junction: "┥",
+        cross: "┿",
+        horizontal: "━",
+        vertical: "│",
+    };
+
+    /// Double horizontal lines joining thin vertical ones, for separators in a `THIN` table.
+    pub const DOUBLE_SEPARATOR: BorderStyle = BorderStyle {
+        top_left: "╒",
+        top_right: "╕",
+        bottom_left: "╘",
+        bottom_right: "╛",
+        top_junction: "╤",
+        bottom_junction: "╧",
+        left_junction: "╞",
+        right_junction: "╡",
+        cross: "╪",
+        horizontal: "═",
+        vertical: "│",
+    };
+
+    pub const ROUNDED: BorderStyle = BorderStyle {
+        top_left: "╭",
+        top_right: "╮",
+        bottom_left: "╰",
+        bottom_right: "╯",
+        ..BorderStyle::THIN
+    };
+
+    pub const BORDERLESS: BorderStyle = BorderStyle {
+        top_left: "",
+        top_right: "",
+        bottom_left: "",
+        bottom_right: "",
+        top_junction: "",
+        bottom_junction: "",
+        left_junction: "",
+        right_junction: "",
+        cross: "",
+        horizontal: "",
+        vertical: "",
+    };
+
+    fn junction(&self, up: bool, down: bool, left: bool, right: bool) -> &'static str {
+        match (up, down, left, right) {
+            (true, true, true, true) => self.cross,
+            (false, true, true, true) => self.top_junction,
+            (true, false, true, true) => self.bottom_junction,
+            (true, true, false, true) => self.left_junction,
+            (true, true, true, false) => self.right_junction,
+            (false, true, false, true) => self.top_left,
+            (false, true, true, false) => self.top_right,
+            (true, false, false, true) => self.bottom_left,
+            (true, false, true, false) => self.bottom_right,
+            (true, true, false, false) => self.vertical,
+            _ => self.horizontal,
+        }
+    }
+}
+
+impl Default for BorderStyle {
+    fn default() -> Self {
+        BorderStyle::THIN
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TitlePosition {
+    /// Inside the top border, falling back to `Above` when there is no room.
+    #[default]
+    Border,
+    Above,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Output {
+    /// Text table drawn with the border style.
+    #[default]
+    Table,
+    /// GitHub-Flavored Markdown pipe table.
+    Markdown,
+    /// HTML `<table>`, with color codes turned into styled `<span>` elements.
+    Html,
+    /// Comma-separated values, quoted as in RFC 4180.
+    Csv,
+    /// Tab-separated values, quoted like `Csv`.
+    Tsv,
+    /// JSON array of objects keyed by the column headers, or `col<index>` for columns without
+    /// one. Repeated keys get a numbered suffix such as `_2`.
+    Json,
+    /// Newline-delimited JSON with one object per row, keyed like `Json`.
+    Ndjson,
+    /// LaTeX `tabular` environment with `\hline` rules.
+    Latex,
+    /// LaTeX `tabular` environment with `\toprule`, `\midrule` and `\bottomrule` from the
+    /// booktabs package.
+    Booktabs,
+    /// reStructuredText grid table. Cell text is never truncated.
+    RstGrid,
+    /// reStructuredText simple table, with multi-line cells joined into one line.
+    RstSimple,
+    /// AsciiDoc `|===` table. Only the last footer row is marked as the footer.
+    AsciiDoc,
+    /// Emacs Org-mode table, with multi-line cells joined into one line.
+    Org,
+    /// MediaWiki `wikitable`.
+    MediaWiki,
+    /// Jira and Confluence wiki markup. Alignment is not supported by the format.
+    Jira,
+    /// `.TS`/`.TE` block for the roff tbl preprocessor, as used in man pages.
+    Roff,
+    /// SVG image of the text table in a monospace font, with color codes turned into fill
+    /// colors.
+    Svg,
+}
+
 impl AsciiTable {
     pub fn new(cell_align: Align, max_width: usize) -> Self {
         Self {
             max_width,
             default_align: cell_align,
-            columns: BTreeMap::new(),
+            ..Self::default()
         }
     }
 
+    /// Prints the table to stdout. Write errors, such as a closed pipe, stop the output
+    /// without panicking.
     pub fn print<L1, L2, T>(&self, data: L1)
     where
         L1: IntoIterator<Item = L2>,
         L2: IntoIterator<Item = T>,
         T: Display,
     {
-        print!("{}", self.format(data))
+        let _ = self.write_to(io::stdout().lock(), data);
     }
 
     pub fn format<L1, L2, T>(&self, data: L1) -> String
@@ -169,47 +531,349 @@ impl AsciiTable {
         L2: IntoIterator<Item = T>,
         T: Display,
     {
-        self.format_inner(self.stringify(data))
+        let mut result = String::new();
+        let _ = self.fmt_to(&mut result, data);
+        result
+    }
+
+    /// Writes the table line by line to `writer`, without building the whole output in memory.
+    pub fn write_to<W, L1, L2, T>(&self, writer: W, data: L1) -> io::Result<()>
+    where
+        W: io::Write,
+        L1: IntoIterator<Item = L2>,
+        L2: IntoIterator<Item = T>,
+        T: Display,
+    {
+        write_io(writer, |out| self.fmt_to(out, data))
     }
 
-    fn format_inner(&self, data: Vec<Vec<SmartString>>) -> String {
-        let num_cols = data.iter().map(|row| row.len()).max().unwrap_or(0);
-        if !self.valid(&data, num_cols) {
-            return self.format_empty();
+    /// Writes the table line by line to a `fmt::Write` sink, such as a `String` or a
+    /// `fmt::Formatter`.
+    pub fn fmt_to<W, L1, L2, T>(&self, mut writer: W, data: L1) -> fmt::Result
+    where
+        W: fmt::Write,
+        L1: IntoIterator<Item = L2>,
+        L2: IntoIterator<Item = T>,
+        T: Display,
+    {
+        self.write_inner(self.stringify(data), &mut writer)
+    }
+
+    /// Writes the table to `writer` while the rows are still arriving, so `data` may be an
+    /// unbounded iterator such as a log tail or a database cursor.
+    ///
+    /// Column widths come from `Column::width` where it is set, and are otherwise measured from
+    /// the first `sample` rows, at least one. Text in later rows that does not fit is truncated
+    /// or wrapped according to the column's overflow. The number of columns is taken from the
+    /// sampled rows as in [`write_to`](Self::write_to); extra cells in later rows are dropped.
+    /// Row spans are ignored.
+    pub fn write_stream<W, L1, L2, T>(&self, writer: W, data: L1, sample: usize) -> io::Result<()>
+    where
+        W: io::Write,
+        L1: IntoIterator<Item = L2>,
+        L2: IntoIterator<Item = T>,
+        T: Display,
+    {
+        write_io(writer, |out| self.fmt_stream(out, data, sample))
+    }
+
+    /// Like [`write_stream`](Self::write_stream), but writes to a `fmt::Write` sink.
+    pub fn fmt_stream<W, L1, L2, T>(&self, mut writer: W, data: L1, sample: usize) -> fmt::Result
+    where
+        W: fmt::Write,
+        L1: IntoIterator<Item = L2>,
+        L2: IntoIterator<Item = T>,
+        T: Display,
+    {
+        let data = data
+            .into_iter()
+            .map(|row| row.into_iter().map(SmartString::from).collect());
+        self.stream_inner(data, sample, &mut writer)
+    }
+
+    fn stream_inner<I>(&self, data: I, sample: usize, out: &mut dyn fmt::Write) -> fmt::Result
+    where
+        I: Iterator<Item = Vec<SmartString>>,
+    {
+        let mut data = data.peekable();
+        let sampled: Vec<_> = data.by_ref().take(sample.max(1)).collect();
+        let num_cols = self.num_cols(&sampled);
+        if self.output != Output::Table {
+            let mut rows = sampled
+                .into_iter()
+                .chain(data)
+                .map(|row| self.square_row(row, num_cols));
+            return self.write_output(out, num_cols, &mut rows);
+        }
+        let max_width = self.width_limit(terminal_width());
+        if (sampled.is_empty() && data.peek().is_none() && self.footer.is_empty())
+            || num_cols == 0
+            || max_width < self.smallest_width(num_cols)
+        {
+            return out.write_str(&self.format_empty());
+        }
+
+        let header = self.stringify_header(num_cols);
+        let footer = self.stringify_footer(num_cols);
+        let mut head = Vec::new();
+        if !self.column_groups.is_empty() {
+            head.push(self.group_row(num_cols));
+        }
+        if header.iter().any(|text| !text.is_empty()) {
+            head.push(self.header_row(header));
+        }
+        let mut covered = vec![0; num_cols];
+        let mut square = |a: usize, row: Vec<SmartString>| {
+            let row = self.square_row(row, num_cols);
+            self.data_row(a, row, &mut covered, a)
+        };
+        let sampled: Vec<_> = sampled
+            .into_iter()
+            .enumerate()
+            .map(|(a, row)| square(a, row))
+            .collect();
+        let footer: Vec<_> = footer.into_iter().map(|row| self.footer_row(row)).collect();
+
+        let cells: Vec<_> = head
+            .iter()
+            .chain(&sampled)
+            .chain(&footer)
+            .flatten()
+            .collect();
+        let widths = self.column_widths(&cells, num_cols, max_width);
+        let mut above = None;
+        for row in &head {
+            self.write_row(out, &widths, row, &mut above, Some(&self.border))?;
+        }
+        let num_sampled = sampled.len();
+        for (a, row) in sampled.into_iter().enumerate() {
+            let rule = self.stream_rule(a);
+            self.write_row(out, &widths, &row, &mut above, rule)?;
+        }
+        for (a, row) in data.enumerate() {
+            let a = a + num_sampled;
+            let row = square(a, row);
+            self.write_row(out, &widths, &row, &mut above, self.stream_rule(a))?;
+        }
+        let footer_separator = self.footer_separator.as_ref().unwrap_or(&self.border);
+        for (a, row) in footer.iter().enumerate() {
+            let rule = if a == 0 { Some(footer_separator) } else { None };
+            self.write_row(out, &widths, row, &mut above, rule)?;
+        }
+        out.write_str(&self.format_last(&widths, &above.unwrap_or_default()))
+    }
+
+    fn stream_rule(&self, row: usize) -> Option<&BorderStyle> {
+        match row {
+            0 => Some(&self.border),
+            _ => self.separator(row - 1),
+        }
+    }
+
+    fn write_row(
+        &self,
+        out: &mut dyn fmt::Write,
+        widths: &[usize],
+        row: &[Cell],
+        above: &mut Option<Vec<usize>>,
+        rule: Option<&BorderStyle>,
+    ) -> fmt::Result {
+        let starts: Vec<_> = row.iter().map(|cell| cell.column).collect();
+        match (above.as_deref(), rule) {
+            (None, _) => out.write_str(&self.format_first(widths, &starts))?,
+            (Some(above), Some(style)) => {
+                let rule = self.format_rule(widths, style, Some(above), Some(&starts), &mut []);
+                out.write_str(&rule)?
+            }
+            (Some(_), None) => {}
+        }
+        let height = row
+            .iter()
+            .map(|cell| self.cell_height(cell, widths))
+            .max()
+            .unwrap_or(1);
+        let regions: Vec<_> = row
+            .iter()
+            .map(|cell| self.format_region(cell, widths, height))
+            .collect();
+        let junctions = vec![self.border.vertical; row.len() + 1];
+        for a in 0..height {
+            let line: Vec<_> = regions.iter().map(|lines| lines[a].clone()).collect();
+            out.write_str(&self.format_line(&line, &junctions))?;
+        }
+        *above = Some(starts);
+        Ok(())
+    }
+
+    fn write_inner(&self, data: Vec<Vec<SmartString>>, out: &mut dyn fmt::Write) -> fmt::Result {
+        let num_cols = self.num_cols(&data);
+        if self.output != Output::Table {
+            let mut rows = self.square_data(data, num_cols).into_iter();
+            return self.write_output(out, num_cols, &mut rows);
+        }
+        self.write_table(data, num_cols, out)
+    }
+
+    // The widest of the rows. Other outputs than the drawn table also count the
+    // configured columns and the footer, so that no header or footer cell is
+    // lost. A table only counts the footer when there are no rows, otherwise its
+    // extra cells are dropped.
+    fn num_cols(&self, rows: &[Vec<SmartString>]) -> usize {
+        let table = matches!(self.output, Output::Table | Output::Svg);
+        let columns = self.columns.keys().filter(|_| !table);
+        let footer = self.footer.iter().filter(|_| !table || rows.is_empty());
+        rows.iter()
+            .map(|row| row.len())
+            .chain(columns.map(|a| a + 1))
+            .chain(footer.map(|row| row.len()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn write_table(
+        &self,
+        data: Vec<Vec<SmartString>>,
+        num_cols: usize,
+        out: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        let max_width = self.width_limit(terminal_width());
+        if !self.valid(&data, num_cols, max_width) {
+            return out.write_str(&self.format_empty());
         }
 
         let header = self.stringify_header(num_cols);
         let data = self.square_data(data, num_cols);
+        let footer = self.stringify_footer(num_cols);
         let has_header = header.iter().any(|text| !text.is_empty());
-        let widths = self.column_widths(&header, &data, num_cols);
+        let footer_separator = self.footer_separator.as_ref().unwrap_or(&self.border);
 
-        let mut result = String::new();
-        result.push_str(&self.format_first(&widths));
+        let mut rows = Vec::new();
+        if !self.column_groups.is_empty() {
+            rows.push((self.group_row(num_cols), Some(&self.border)));
+        }
         if has_header {
-            result.push_str(&self.format_header_row(&header, &widths));
-            result.push_str(&self.format_middle(&widths));
+            rows.push((self.header_row(header), Some(&self.border)));
         }
-        for row in data {
-            result.push_str(&self.format_row(&row, &widths));
+        if data.is_empty() {
+            if let Some((_, separator)) = rows.last_mut() {
+                *separator = Some(footer_separator);
+            }
         }
-        result.push_str(&self.format_last(&widths));
-        result
+        let last_row = data.len().saturating_sub(1);
+        let mut covered = vec![0; num_cols];
+        for (a, row) in data.into_iter().enumerate() {
+            let separator = if a < last_row {
+                self.separator(a)
+            } else {
+                Some(footer_separator)
+            };
+            rows.push((self.data_row(a, row, &mut covered, last_row), separator));
+        }
+        for row in footer {
+            rows.push((self.footer_row(row), None));
+        }
+
+        let cells: Vec<_> = rows.iter().flat_map(|(row, _)| row).collect();
+        let widths = self.column_widths(&cells, num_cols, max_width);
+        let heights = self.row_heights(&rows, &widths);
+        let vertical = self.border.vertical;
+
+        let mut above: Vec<_> = rows[0].0.iter().map(|cell| cell.column).collect();
+        out.write_str(&self.format_first(&widths, &above))?;
+        let mut active = Vec::new();
+        for (a, (row, separator)) in rows.iter().enumerate() {
+            for cell in row {
+                let height = self.region_height(&rows, &heights, a, cell.rows);
+                let lines = self.format_region(cell, &widths, height);
+                active.push((cell, a + cell.rows - 1, lines.into_iter()));
+            }
+            active.sort_by_key(|(cell, _, _)| cell.column);
+            let junctions = vec![vertical; active.len() + 1];
+            for _ in 0..heights[a] {
+                let line: Vec<_> = active
+                    .iter_mut()
+                    .map(|(_, _, lines)| lines.next().unwrap_or_default())
+                    .collect();
+                out.write_str(&self.format_line(&line, &junctions))?;
+            }
+            above = active.iter().map(|(cell, _, _)| cell.column).collect();
+            active.retain(|(_, last, _)| *last > a);
+            if let (Some(style), Some((next, _))) = (separator, rows.get(a + 1)) {
+                let below: Vec<_> = active
+                    .iter()
+                    .map(|(cell, _, _)| cell.column)
+                    .chain(next.iter().map(|cell| cell.column))
+                    .collect();
+                let rule =
+                    self.format_rule(&widths, style, Some(&above), Some(&below), &mut active);
+                out.write_str(&rule)?;
+            }
+        }
+        out.write_str(&self.format_last(&widths, &above))
+    }
+
+    fn write_output(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        match self.output {
+            Output::Table => unreachable!(),
+            Output::Markdown => self.write_markdown(out, num_cols, rows),
+            Output::Html => self.write_html(out, num_cols, rows),
+            Output::Csv => self.write_csv(out, num_cols, rows, ','),
+            Output::Tsv => self.write_csv(out, num_cols, rows, '\t'),
+            Output::Json => self.write_json(out, num_cols, rows, false),
+            Output::Ndjson => self.write_json(out, num_cols, rows, true),
+            Output::Latex => self.write_latex(out, num_cols, rows, false),
+            Output::Booktabs => self.write_latex(out, num_cols, rows, true),
+            Output::RstGrid => self.write_rst(out, num_cols, rows, true),
+            Output::RstSimple => self.write_rst(out, num_cols, rows, false),
+            Output::AsciiDoc => self.write_asciidoc(out, num_cols, rows),
+            Output::Org => self.write_org(out, num_cols, rows),
+            Output::MediaWiki => self.write_mediawiki(out, num_cols, rows),
+            Output::Jira => self.write_jira(out, num_cols, rows),
+            Output::Roff => self.write_roff(out, num_cols, rows),
+            Output::Svg => self.write_svg(out, num_cols, rows),
+        }
+    }
+
+    fn valid(&self, data: &[Vec<SmartString>], num_cols: usize, max_width: usize) -> bool {
+        !(data.is_empty() && self.footer.is_empty())
+            && num_cols != 0
+            && max_width >= self.smallest_width(num_cols)
     }
 
-    fn valid(&self, data: &Vec<Vec<SmartString>>, num_cols: usize) -> bool {
-        if data.len() == 0 {
-            false
-        } else if num_cols == 0 {
-            false
-        } else if self.max_width < Self::smallest_width(num_cols) {
-            false
+    // The terminal width is read once per render and passed in, so that a
+    // resize halfway cannot give parts of the table different limits.
+    fn width_limit(&self, terminal_width: Option<usize>) -> usize {
+        if self.use_terminal_width {
+            terminal_width.unwrap_or(self.max_width)
         } else {
-            true
+            self.max_width
         }
     }
 
-    fn smallest_width(num_cols: usize) -> usize {
-        ((num_cols - 1) * 3) + 4
+    fn smallest_width(&self, num_cols: usize) -> usize {
+        let border_width = self.border.vertical.width();
+        let padding: usize = (0..num_cols)
+            .map(|a| {
+                let (left, right) = self.padding(a);
+                left + right
+            })
+            .sum();
+        self.indent + ((num_cols + 1) * border_width) + padding
+    }
+
+    fn padding(&self, column: usize) -> (usize, usize) {
+        let conf = self.columns.get(&column);
+        (
+            conf.and_then(|c| c.padding_left)
+                .unwrap_or(self.padding_left),
+            conf.and_then(|c| c.padding_right)
+                .unwrap_or(self.padding_right),
+        )
     }
 
     fn stringify<L1, L2, T>(&self, data: L1) -> Vec<Vec<SmartString>>
@@ -234,6 +898,15 @@ impl AsciiTable {
             .collect()
     }
 
+    fn stringify_footer(&self, num_cols: usize) -> Vec<Vec<SmartString>> {
+        let footer = self
+            .footer
+            .iter()
+            .map(|row| row.iter().take(num_cols).map(SmartString::from).collect())
+            .collect();
+        self.square_data(footer, num_cols)
+    }
+
     fn square_data(
         &self,
         mut data: Vec<Vec<SmartString>>,
@@ -247,27 +920,238 @@ impl AsciiTable {
         data
     }
 
-    fn column_widths(
+    fn square_row(&self, row: Vec<SmartString>, num_cols: usize) -> Vec<SmartString> {
+        let row = row.into_iter().take(num_cols).collect();
+        self.square_data(vec![row], num_cols).remove(0)
+    }
+
+    fn column_align(&self, column: usize) -> Align {
+        self.columns
+            .get(&column)
+            .map(|c| c.align)
+            .unwrap_or(self.default_align)
+    }
+
+    fn group_row(&self, num_cols: usize) -> Vec<Cell> {
+        let mut result = Vec::new();
+        let mut column = 0;
+        while column < num_cols {
+            let cell = match self.column_groups.get(&column) {
+                Some(group) => Cell {
+                    text: SmartString::from(&group.header),
+                    column,
+                    span: group.span.max(1).min(num_cols - column),
+                    rows: 1,
+                    align: group.align,
+                    vertical_align: DEFAULT_VERTICAL_ALIGN,
+                },
+                None => Cell {
+                    text: SmartString::new(),
+                    column,
+                    span: 1,
+                    rows: 1,
+                    align: self.default_align,
+                    vertical_align: DEFAULT_VERTICAL_ALIGN,
+                },
+            };
+            column += cell.span;
+            result.push(cell);
+        }
+        result
+    }
+
+    fn header_row(&self, row: Vec<SmartString>) -> Vec<Cell> {
+        self.label_row(row, |c| c.header_align, self.header_align)
+    }
+
+    fn footer_row(&self, row: Vec<SmartString>) -> Vec<Cell> {
+        self.label_row(row, |c| c.footer_align, self.footer_align)
+    }
+
+    fn label_row(
         &self,
-        header: &[SmartString],
-        data: &[Vec<SmartString>],
-        num_cols: usize,
+        row: Vec<SmartString>,
+        column_align: fn(&Column) -> Option<Align>,
+        table_align: Option<Align>,
+    ) -> Vec<Cell> {
+        row.into_iter()
+            .enumerate()
+            .map(|(column, text)| {
+                let conf = self.columns.get(&column);
+                let align = conf
+                    .and_then(column_align)
+                    .or(table_align)
+                    .or_else(|| conf.map(|c| c.align))
+                    .unwrap_or(self.default_align);
+                Cell {
+                    text,
+                    column,
+                    span: 1,
+                    rows: 1,
+                    align,
+                    vertical_align: self.vertical_align(column),
+                }
+            })
+            .collect()
+    }
+
+    fn data_row(
+        &self,
+        index: usize,
+        row: Vec<SmartString>,
+        covered: &mut [usize],
+        last_row: usize,
+    ) -> Vec<Cell> {
+        let mut result: Vec<Cell> = Vec::new();
+        for (column, text) in row.into_iter().enumerate() {
+            if let Some(cell) = result.last() {
+                if column < cell.column + cell.span {
+                    continue;
+                }
+            }
+            if covered[column] > 0 {
+                covered[column] -= 1;
+                continue;
+            }
+            let span = self.col_spans.get(&(index, column)).copied().unwrap_or(1);
+            let span = covered[column..]
+                .iter()
+                .take(span.max(1))
+                .take_while(|&&x| x == 0)
+                .count();
+            let rows = self.row_spans.get(&(index, column)).copied().unwrap_or(1);
+            let rows = rows.max(1).min(last_row - index + 1);
+            for x in &mut covered[column..column + span] {
+                *x = rows - 1;
+            }
+            result.push(Cell {
+                text,
+                column,
+                span,
+                rows,
+                align: self.column_align(column),
+                vertical_align: self.vertical_align(column),
+            });
+        }
+        result
+    }
+
+    fn vertical_align(&self, column: usize) -> VerticalAlign {
+        self.columns
+            .get(&column)
+            .map(|c| c.vertical_align)
+            .unwrap_or(DEFAULT_VERTICAL_ALIGN)
+    }
+
+    fn row_heights(
+        &self,
+        rows: &[(Vec<Cell>, Option<&BorderStyle>)],
+        widths: &[usize],
     ) -> Vec<usize> {
-        let result: Vec<_> = (0..num_cols)
+        let mut result: Vec<_> = rows
+            .iter()
+            .map(|(row, _)| {
+                row.iter()
+                    .filter(|cell| cell.rows == 1)
+                    .map(|cell| self.cell_height(cell, widths))
+                    .max()
+                    .unwrap_or(1)
+            })
+            .collect();
+        for (a, (row, _)) in rows.iter().enumerate() {
+            for cell in row.iter().filter(|cell| cell.rows > 1) {
+                let height = self.cell_height(cell, widths);
+                let available = self.region_height(rows, &result, a, cell.rows);
+                if height > available {
+                    result[a + cell.rows - 1] += height - available;
+                }
+            }
+        }
+        result
+    }
+
+    fn region_height(
+        &self,
+        rows: &[(Vec<Cell>, Option<&BorderStyle>)],
+        heights: &[usize],
+        start: usize,
+        span: usize,
+    ) -> usize {
+        let rules = rows[start..start + span - 1]
+            .iter()
+            .filter(|(_, separator)| separator.is_some_and(|style| !style.horizontal.is_empty()))
+            .count();
+        heights[start..start + span].iter().sum::<usize>() + rules
+    }
+
+    fn cell_height(&self, cell: &Cell, widths: &[usize]) -> usize {
+        let width = self.span_width(widths, cell.column, cell.span);
+        self.format_cell_lines(&cell.text, width, cell.align, cell.column)
+            .len()
+    }
+
+    fn column_widths(&self, cells: &[&Cell], num_cols: usize, max_width: usize) -> Vec<usize> {
+        let widths = self.content_widths(cells, num_cols, true);
+        self.truncate_widths(widths, max_width)
+    }
+
+    // Widths that fit the content of the cells, within the column limits if
+    // `limited` is set.
+    fn content_widths(&self, cells: &[&Cell], num_cols: usize, limited: bool) -> Vec<usize> {
+        let default_conf = &DEFAULT_COLUMN;
+        let conf = |a| self.columns.get(&a).unwrap_or(default_conf);
+        let max_widths: Vec<_> = (0..num_cols)
             .map(|a| {
-                let default_conf = &DEFAULT_COLUMN;
-                let conf = self.columns.get(&a).unwrap_or(default_conf);
-                let column_width = data.iter().map(|row| row[a].char_len()).max().unwrap();
-                let header_width = header[a].char_len();
-                column_width.max(header_width).min(conf.max_width)
+                if limited {
+                    conf(a).max_width
+                } else {
+                    usize::MAX
+                }
             })
             .collect();
-        self.truncate_widths(result)
+        let fixed_widths: Vec<_> = (0..num_cols)
+            .map(|a| conf(a).width.filter(|_| limited))
+            .collect();
+        let mut result: Vec<_> = (0..num_cols)
+            .map(|a| {
+                let column_width = cells
+                    .iter()
+                    .filter(|cell| cell.column == a && cell.span == 1)
+                    .map(|cell| cell.text.line_len())
+                    .max()
+                    .unwrap_or(0);
+                fixed_widths[a].unwrap_or(column_width.min(max_widths[a]))
+            })
+            .collect();
+
+        let mut spanning: Vec<_> = cells.iter().filter(|cell| cell.span > 1).collect();
+        spanning.sort_by_key(|cell| cell.span);
+        for cell in spanning {
+            let columns = cell.column..cell.column + cell.span;
+            while self.span_width(&result, cell.column, cell.span) < cell.text.line_len() {
+                let narrowest = columns
+                    .clone()
+                    .filter(|&a| fixed_widths[a].is_none() && result[a] < max_widths[a])
+                    .min_by_key(|&a| result[a]);
+                match narrowest {
+                    Some(a) => result[a] += 1,
+                    None => break,
+                }
+            }
+        }
+        result
     }
 
-    fn truncate_widths(&self, mut widths: Vec<usize>) -> Vec<usize> {
-        let max_width = self.max_width;
-        let table_padding = Self::smallest_width(widths.len());
+    fn span_width(&self, widths: &[usize], column: usize, span: usize) -> usize {
+        let border_width = self.border.vertical.width();
+        let inner: usize = (column..column + span - 1)
+            .map(|a| self.padding(a).1 + border_width + self.padding(a + 1).0)
+            .sum();
+        widths[column..column + span].iter().sum::<usize>() + inner
+    }
+
+    fn truncate_widths(&self, mut widths: Vec<usize>, max_width: usize) -> Vec<usize> {
+        let table_padding = self.smallest_width(widths.len());
         while widths.iter().sum::<usize>() + table_padding > max_width
             && *widths.iter().max().unwrap() > 0
         {
@@ -278,142 +1162,336 @@ impl AsciiTable {
         widths
     }
 
-    fn format_line(&self, row: &[SmartString], head: &str, delim: &str, tail: &str) -> String {
-        let mut result = String::new();
-        result.push_str(head);
-        for cell in row {
-            result.push_str(&format!("{}{}", cell, delim));
+    fn format_line(&self, row: &[String], junctions: &[&str]) -> String {
+        let mut result = " ".repeat(self.indent);
+        for (junction, cell) in junctions.iter().zip(row) {
+            result.push_str(junction);
+            result.push_str(cell);
         }
-        for _ in 0..delim.chars().count() {
-            result.pop();
-        }
-        result.push_str(tail);
+        result.push_str(junctions[row.len()]);
         result.push('\n');
         result
     }
 
     fn format_empty(&self) -> String {
-        self.format_first(&vec![0])
+        let (padding_left, padding_right) = self.padding(0);
+        let vertical = self.border.vertical;
+        self.format_first(&[0], &[0])
             + &self.format_line(
-                &[SmartString::new()],
-                &format!("{}{}", NS, ' '),
-                &format!("{}{}{}", ' ', NS, ' '),
-                &format!("{}{}", ' ', NS),
+                &[" ".repeat(padding_left + padding_right)],
+                &[vertical, vertical],
             )
-            + &self.format_last(&[0])
+            + &self.format_last(&[0], &[0])
     }
 
-    fn format_first(&self, widths: &[usize]) -> String {
-        let row: Vec<_> = widths
-            .iter()
-            .map(|&x| SmartString::from_visible(EW.repeat(x)))
-            .collect();
-        self.format_line(
-            &row,
-            &format!("{}{}", SE, EW),
-            &format!("{}{}{}", EW, EWS, EW),
-            &format!("{}{}", EW, SW),
-        )
+    fn separator(&self, row: usize) -> Option<&BorderStyle> {
+        self.separators.get(&row).or(if self.separate_rows {
+            Some(&self.border)
+        } else {
+            None
+        })
     }
 
-    fn format_middle(&self, widths: &[usize]) -> String {
-        let row: Vec<_> = widths
-            .iter()
-            .map(|&x| SmartString::from_visible(EW.repeat(x)))
+    fn format_rule(
+        &self,
+        widths: &[usize],
+        style: &BorderStyle,
+        above: Option<&[usize]>,
+        below: Option<&[usize]>,
+        through: &mut [(&Cell, usize, std::vec::IntoIter<String>)],
+    ) -> String {
+        let line = style.horizontal;
+        if line.is_empty() {
+            return String::new();
+        }
+        let num_cols = widths.len();
+        let boundary = |row: Option<&[usize]>, column: usize| {
+            row.is_some_and(|row| column == num_cols || row.contains(&column))
+        };
+        let covering: Vec<_> = (0..num_cols)
+            .map(|a| {
+                through
+                    .iter()
+                    .position(|(cell, _, _)| (cell.column..cell.column + cell.span).contains(&a))
+            })
             .collect();
-        self.format_line(
-            &row,
-            &format!("{}{}", NES, EW),
-            &format!("{}{}{}", EW, NEWS, EW),
-            &format!("{}{}", EW, NWS),
-        )
+        let mut row = Vec::new();
+        let mut junctions = Vec::new();
+        let mut a = 0;
+        loop {
+            let left = a > 0 && covering[a - 1].is_none();
+            let right = a < num_cols && covering[a].is_none();
+            let junction = style.junction(boundary(above, a), boundary(below, a), left, right);
+            junctions.push(junction);
+            if a == num_cols {
+                break;
+            }
+            match covering[a] {
+                Some(b) => {
+                    let (cell, _, lines) = &mut through[b];
+                    row.push(lines.next().unwrap_or_default());
+                    a += cell.span;
+                }
+                None => {
+                    let (padding_left, padding_right) = self.padding(a);
+                    row.push(line.repeat(padding_left + widths[a] + padding_right));
+                    a += 1;
+                }
+            }
+        }
+        self.format_line(&row, &junctions)
     }
 
-    fn format_row(&self, row: &[SmartString], widths: &[usize]) -> String {
-        let row: Vec<_> = (0..widths.len())
-            .map(|a| {
-                let cell = &row[a];
-                let width = widths[a];
-                let align = self
-                    .columns
-                    .get(&a)
-                    .map(|c| c.align)
-                    .unwrap_or(self.default_align);
-                self.format_cell(cell, width, ' ', align)
-            })
+    fn format_first(&self, widths: &[usize], below: &[usize]) -> String {
+        let border = &self.border;
+        let rule = self.format_rule(widths, border, None, Some(below), &mut []);
+        if self.title.is_empty() {
+            return rule;
+        }
+        let title = SmartString::from(&self.title);
+        let width = self.table_width(widths);
+        let border_width = border.vertical.width();
+        if self.title_position == TitlePosition::Border
+            && !rule.is_empty()
+            && title.lines().len() == 1
+            && width >= (border_width * 2) + 5
+        {
+            self.format_title_rule(&rule, &title, width)
+        } else {
+            self.format_caption(&title, width, self.title_align) + &rule
+        }
+    }
+
+    fn format_title_rule(&self, rule: &str, title: &SmartString, width: usize) -> String {
+        let border_width = self.border.vertical.width();
+        let space = width - (border_width * 2) - 2;
+        let title = self.fit(title, space - 2);
+        let text_width = title.char_len() + 2;
+        let offset = border_width
+            + 1
+            + match self.title_align {
+                Align::Left => 0,
+                Align::Center => (space - text_width) / 2,
+                Align::Right => space - text_width,
+            };
+        let mut glyphs: Vec<_> = rule
+            .trim_end_matches('\n')
+            .chars()
+            .skip(self.indent)
+            .map(String::from)
             .collect();
-        self.format_line(
-            &row,
-            &format!("{}{}", NS, ' '),
-            &format!("{}{}{}", ' ', NS, ' '),
-            &format!("{}{}", ' ', NS),
-        )
+        let text = format!(" {} ", title);
+        let replacement = std::iter::once(text).chain(std::iter::repeat(String::new()));
+        glyphs.splice(offset..offset + text_width, replacement.take(text_width));
+        format!("{}{}\n", " ".repeat(self.indent), glyphs.concat())
     }
 
-    fn format_header_row(&self, row: &[SmartString], widths: &[usize]) -> String {
-        let row: Vec<_> = row
+    fn format_caption(&self, text: &SmartString, width: usize, align: Align) -> String {
+        text.lines()
             .iter()
-            .zip(widths.iter())
-            .map(|(cell, &width)| self.format_cell(cell, width, ' ', Align::Left))
-            .collect();
-        self.format_line(
-            &row,
-            &format!("{}{}", NS, ' '),
-            &format!("{}{}{}", ' ', NS, ' '),
-            &format!("{}{}", ' ', NS),
-        )
+            .map(|line| {
+                let line = self.fit(line, width);
+                let space = width - line.char_len();
+                let pad = match align {
+                    Align::Left => 0,
+                    Align::Center => space / 2,
+                    Align::Right => space,
+                };
+                format!("{}{}\n", " ".repeat(self.indent + pad), line)
+            })
+            .collect()
+    }
+
+    fn fit(&self, text: &SmartString, width: usize) -> SmartString {
+        if text.char_len() > width {
+            let conf = &DEFAULT_COLUMN;
+            text.truncate(width, conf.truncation, &conf.truncation_marker)
+        } else {
+            text.clone()
+        }
     }
 
-    fn format_last(&self, widths: &[usize]) -> String {
-        let row: Vec<_> = widths
+    fn table_width(&self, widths: &[usize]) -> usize {
+        let border_width = self.border.vertical.width();
+        let cells: usize = widths
             .iter()
-            .map(|&x| SmartString::from_visible(EW.repeat(x)))
-            .collect();
-        self.format_line(
-            &row,
-            &format!("{}{}", NE, EW),
-            &format!("{}{}{}", EW, NEW, EW),
-            &format!("{}{}", EW, NW),
-        )
+            .enumerate()
+            .map(|(a, width)| {
+                let (padding_left, padding_right) = self.padding(a);
+                padding_left + width + padding_right + border_width
+            })
+            .sum();
+        cells + border_width
     }
 
-    fn format_cell(&self, text: &SmartString, len: usize, pad: char, align: Align) -> SmartString {
-        if text.char_len() > len {
-            let mut result = text.clone();
-            while result.char_len() > len {
-                result.pop();
-            }
-            if result.pop().is_some() {
-                result.push_visible('+')
-            }
-            result
+    fn format_region(&self, cell: &Cell, widths: &[usize], height: usize) -> Vec<String> {
+        let width = self.span_width(widths, cell.column, cell.span);
+        let lines = self.format_cell_lines(&cell.text, width, cell.align, cell.column);
+        let space = height.saturating_sub(lines.len());
+        let top = match cell.vertical_align {
+            VerticalAlign::Top => 0,
+            VerticalAlign::Middle => space / 2,
+            VerticalAlign::Bottom => space,
+        };
+        let (padding_left, _) = self.padding(cell.column);
+        let (_, padding_right) = self.padding(cell.column + cell.span - 1);
+        let blank = " ".repeat(width);
+        (0..top)
+            .map(|_| blank.clone())
+            .chain(lines.iter().map(|line| line.to_string()))
+            .chain(std::iter::repeat(blank.clone()))
+            .take(height)
+            .map(|text| {
+                format!(
+                    "{}{}{}",
+                    " ".repeat(padding_left),
+                    text,
+                    " ".repeat(padding_right)
+                )
+            })
+            .collect()
+    }
+
+    fn format_cell_lines(
+        &self,
+        text: &SmartString,
+        len: usize,
+        align: Align,
+        column: usize,
+    ) -> Vec<SmartString> {
+        let default_conf = &DEFAULT_COLUMN;
+        let conf = self.columns.get(&column).unwrap_or(default_conf);
+        let lines = text
+            .lines()
+            .into_iter()
+            .flat_map(|line| match conf.overflow {
+                Overflow::Truncate => vec![line],
+                Overflow::Wrap => line.wrap(len, false),
+                Overflow::WrapWords => line.wrap(len, true),
+            });
+        lines
+            .map(|line| self.format_cell(&line, len, ' ', align, conf))
+            .collect()
+    }
+
+    fn format_last(&self, widths: &[usize], above: &[usize]) -> String {
+        let border = &self.border;
+        let rule = self.format_rule(widths, border, Some(above), None, &mut []);
+        if self.caption.is_empty() {
+            return rule;
+        }
+        let caption = SmartString::from(&self.caption);
+        rule + &self.format_caption(&caption, self.table_width(widths), self.caption_align)
+    }
+
+    fn format_cell(
+        &self,
+        text: &SmartString,
+        len: usize,
+        pad: char,
+        align: Align,
+        conf: &Column,
+    ) -> SmartString {
+        let mut result = if text.char_len() > len {
+            text.truncate(len, conf.truncation, &conf.truncation_marker)
         } else {
-            let mut result = text.clone();
-            match align {
-                Align::Left => {
-                    while result.char_len() < len {
-                        result.push_visible(pad)
-                    }
+            text.clone()
+        };
+        match align {
+            Align::Left => {
+                while result.char_len() < len {
+                    result.push_visible(pad)
                 }
-                Align::Right => {
-                    while result.char_len() < len {
-                        result.lpush_visible(pad)
-                    }
+            }
+            Align::Right => {
+                while result.char_len() < len {
+                    result.lpush_visible(pad)
                 }
-                Align::Center => {
-                    while result.char_len() < len {
-                        result.push_visible(pad);
-                        if result.char_len() < len {
-                            result.lpush_visible(pad)
-                        }
+            }
+            Align::Center => {
+                while result.char_len() < len {
+                    result.push_visible(pad);
+                    if result.char_len() < len {
+                        result.lpush_visible(pad)
                     }
                 }
             }
-            result
         }
+        result
+    }
+}
+
+/// Returns the width of the terminal attached to stdout, or `None` if stdout is not a terminal.
+///
+/// The width is read with the `TIOCGWINSZ` ioctl on Unix, falling back to the `COLUMNS`
+/// environment variable.
+pub fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    ioctl_width()
+        .or_else(|| env::var("COLUMNS").ok()?.trim().parse().ok())
+        .filter(|&width| width > 0)
+}
+
+#[cfg(unix)]
+fn ioctl_width() -> Option<usize> {
+    // SAFETY: `winsize` is a plain C struct of integers, so all-zero bytes are a valid value.
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    // SAFETY: `TIOCGWINSZ` only writes a `winsize` through the pointer, which refers to a live,
+    // properly aligned local. An invalid or non-terminal descriptor makes the call fail instead.
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    match result {
+        0 => Some(usize::from(size.ws_col)),
+        _ => None,
     }
 }
 
+#[cfg(not(unix))]
+fn ioctl_width() -> Option<usize> {
+    None
+}
+
+fn write_io<W, F>(writer: W, f: F) -> io::Result<()>
+where
+    W: io::Write,
+    F: FnOnce(&mut IoAdapter<W>) -> fmt::Result,
+{
+    let mut adapter = IoAdapter {
+        inner: writer,
+        error: None,
+    };
+    match f(&mut adapter) {
+        Ok(()) => Ok(()),
+        Err(fmt::Error) => Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "formatter error"))),
+    }
+}
+
+struct IoAdapter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+struct Cell {
+    text: SmartString,
+    column: usize,
+    span: usize,
+    rows: usize,
+    align: Align,
+    vertical_align: VerticalAlign,
+}
+
 #[derive(Clone, Debug)]
 struct SmartString {
     fragments: Vec<(bool, String)>,
@@ -454,7 +1532,7 @@ impl SmartString {
                     }
                     visible = !visible;
                     buf = String::new();
-                } else if ch != '[' && ch != ';' && !('0'..='9').contains(&ch) {
+                } else if ch != '[' && ch != ';' && !ch.is_ascii_digit() {
                     if !buf.is_empty() {
                         fragments.push((visible, buf));
                     }
@@ -473,12 +1551,6 @@ impl SmartString {
         Self { fragments }
     }
 
-    fn from_visible(string: String) -> Self {
-        Self {
-            fragments: vec![(true, string)],
-        }
-    }
-
     fn char_len(&self) -> usize {
         self.fragments
             .iter()
@@ -487,6 +1559,95 @@ impl SmartString {
             .sum()
     }
 
+    fn visible_chars(&self) -> Vec<char> {
+        self.fragments
+            .iter()
+            .filter(|(visible, _)| *visible)
+            .flat_map(|(_, string)| string.chars())
+            .collect()
+    }
+
+    // Keeps the visible characters in `start..end` and every color code, so
+    // that the colors of the original text still apply to the slice.
+    fn slice(&self, start: usize, end: usize) -> Self {
+        let mut index = 0;
+        let fragments = self
+            .fragments
+            .iter()
+            .map(|(visible, string)| {
+                if *visible {
+                    let result = string
+                        .chars()
+                        .enumerate()
+                        .filter(|(a, _)| (start..end).contains(&(index + a)))
+                        .map(|(_, ch)| ch)
+                        .collect();
+                    index += string.chars().count();
+                    (true, result)
+                } else {
+                    (false, string.clone())
+                }
+            })
+            .collect();
+        Self { fragments }
+    }
+
+    fn lines(&self) -> Vec<Self> {
+        let chars = self.visible_chars();
+        if !chars.contains(&'\n') {
+            return vec![self.clone()];
+        }
+        let mut result = Vec::new();
+        let mut start = 0;
+        for (a, &ch) in chars.iter().enumerate() {
+            if ch == '\n' {
+                let end = if a > start && chars[a - 1] == '\r' {
+                    a - 1
+                } else {
+                    a
+                };
+                result.push(self.slice(start, end));
+                start = a + 1;
+            }
+        }
+        result.push(self.slice(start, chars.len()));
+        result
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines()
+            .iter()
+            .map(|line| line.char_len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn wrap(&self, width: usize, words: bool) -> Vec<Self> {
+        if width == 0 || self.char_len() <= width {
+            return vec![self.clone()];
+        }
+        let chars = self.visible_chars();
+        let widths: Vec<_> = chars.iter().map(|ch| ch.width_cjk().unwrap_or(0)).collect();
+        let ranges = if words {
+            wrap_words(&chars, &widths, width)
+        } else {
+            wrap_chars(&widths, 0, chars.len(), width)
+        };
+        ranges
+            .into_iter()
+            .map(|(start, end)| self.slice(start, end))
+            .collect()
+    }
+
+    // Returns the visible text without color codes.
+    fn plain(&self) -> String {
+        self.fragments
+            .iter()
+            .filter(|(visible, _)| *visible)
+            .map(|(_, string)| string.as_str())
+            .collect()
+    }
+
     fn is_empty(&self) -> bool {
         self.fragments
             .iter()
@@ -494,12 +1655,77 @@ impl SmartString {
             .all(|(_, string)| string.is_empty())
     }
 
-    fn pop(&mut self) -> Option<char> {
-        self.fragments
+    // Replaces the visible characters in `start..end` and keeps every color
+    // code.
+    fn splice(&self, start: usize, end: usize, replacement: &str) -> Self {
+        let mut result = self.clone();
+        let mut index = 0;
+        let mut replaced = false;
+        let fragments = result
+            .fragments
             .iter_mut()
-            .filter(|(visible, string)| *visible && !string.is_empty())
-            .last()
-            .and_then(|(_, string)| string.pop())
+            .filter(|(visible, _)| *visible)
+            .map(|(_, string)| string);
+        for string in fragments {
+            let mut spliced = String::new();
+            for ch in string.chars() {
+                if index == start {
+                    spliced.push_str(replacement);
+                    replaced = true;
+                }
+                if !(start..end).contains(&index) {
+                    spliced.push(ch);
+                }
+                index += 1;
+            }
+            *string = spliced;
+        }
+        if !replaced {
+            for ch in replacement.chars() {
+                result.push_visible(ch);
+            }
+        }
+        result
+    }
+
+    fn truncate(&self, len: usize, truncation: Truncation, marker: &str) -> Self {
+        let mut marker = marker.to_string();
+        while marker.width_cjk() > len {
+            marker.pop();
+        }
+        let keep = len - marker.width_cjk();
+        let widths: Vec<_> = self
+            .visible_chars()
+            .iter()
+            .map(|ch| ch.width_cjk().unwrap_or(0))
+            .collect();
+        let prefix = |budget: usize| {
+            let mut width = 0;
+            widths
+                .iter()
+                .take_while(|&&x| {
+                    width += x;
+                    width <= budget
+                })
+                .count()
+        };
+        let suffix = |budget: usize| {
+            let mut width = 0;
+            widths.len()
+                - widths
+                    .iter()
+                    .rev()
+                    .take_while(|&&x| {
+                        width += x;
+                        width <= budget
+                    })
+                    .count()
+        };
+        match truncation {
+            Truncation::End => self.splice(prefix(keep), widths.len(), &marker),
+            Truncation::Start => self.splice(0, suffix(keep), &marker),
+            Truncation::Middle => self.splice(prefix(keep - keep / 2), suffix(keep / 2), &marker),
+        }
     }
 
     fn push_visible(&mut self, ch: char) {
@@ -531,6 +1757,63 @@ impl SmartString {
     }
 }
 
+fn wrap_chars(widths: &[usize], start: usize, end: usize, max: usize) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut line_start = start;
+    let mut line_width = 0;
+    for (a, &width) in widths.iter().enumerate().take(end).skip(start) {
+        if line_width + width > max && a > line_start {
+            result.push((line_start, a));
+            line_start = a;
+            line_width = 0;
+        }
+        line_width += width;
+    }
+    result.push((line_start, end));
+    result
+}
+
+fn wrap_words(chars: &[char], widths: &[usize], max: usize) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut line: Option<(usize, usize, usize)> = None;
+    let mut a = 0;
+    while a < chars.len() {
+        if chars[a].is_whitespace() {
+            a += 1;
+            continue;
+        }
+        let start = a;
+        while a < chars.len() && !chars[a].is_whitespace() {
+            a += 1;
+        }
+        let word_width: usize = widths[start..a].iter().sum();
+        if let Some((line_start, line_end, line_width)) = line {
+            let gap_width: usize = widths[line_end..start].iter().sum();
+            if line_width + gap_width + word_width <= max {
+                line = Some((line_start, a, line_width + gap_width + word_width));
+                continue;
+            }
+            result.push((line_start, line_end));
+        }
+        if word_width <= max {
+            line = Some((start, a, word_width));
+        } else {
+            let mut pieces = wrap_chars(widths, start, a, max);
+            let (piece_start, piece_end) = pieces.pop().unwrap();
+            result.extend(pieces);
+            let piece_width = widths[piece_start..piece_end].iter().sum();
+            line = Some((piece_start, piece_end, piece_width));
+        }
+    }
+    if let Some((line_start, line_end, _)) = line {
+        result.push((line_start, line_end));
+    }
+    if result.is_empty() {
+        result.push((0, 0));
+    }
+    result
+}
+
 impl Display for SmartString {
     fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
         let concat: String = self
@@ -0,0 +1,61 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Align, AsciiTable, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_markdown(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        write_row(out, &self.stringify_header(num_cols))?;
+        let delimiters: Vec<_> = (0..num_cols)
+            .map(|a| match self.column_align(a) {
+                Align::Left => ":---",
+                Align::Center => ":---:",
+                Align::Right => "---:",
+            })
+            .collect();
+        writeln!(out, "| {} |", delimiters.join(" | "))?;
+        for row in rows.chain(self.stringify_footer(num_cols)) {
+            write_row(out, &row)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_row(out: &mut dyn fmt::Write, row: &[SmartString]) -> fmt::Result {
+    let cells: Vec<_> = row.iter().map(escape).collect();
+    writeln!(out, "| {} |", cells.join(" | "))
+}
+
+// Pipes would end the cell and line breaks the row, so they are escaped and
+// replaced with `<br>`.
+fn escape(text: &SmartString) -> String {
+    let lines: Vec<_> = text
+        .lines()
+        .iter()
+        .map(|line| line.plain().replace('|', "\\|"))
+        .collect();
+    lines.join("<br>")
+}
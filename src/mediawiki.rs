@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Align, AsciiTable, Cell, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_mediawiki(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        writeln!(out, "{{| class=\"wikitable\"")?;
+        let header = self.stringify_header(num_cols);
+        if header.iter().any(|text| !text.is_empty()) {
+            write_row(out, "!", &self.header_row(header))?;
+        }
+        for row in rows {
+            writeln!(out, "|-")?;
+            write_row(out, "|", &self.label_row(row, |_| None, None))?;
+        }
+        for row in self.stringify_footer(num_cols) {
+            writeln!(out, "|-")?;
+            write_row(out, "|", &self.footer_row(row))?;
+        }
+        writeln!(out, "|}}")
+    }
+}
+
+// Cells that are not left aligned carry their own `text-align` style, since
+// MediaWiki has no column alignment.
+fn write_row(out: &mut dyn fmt::Write, marker: &str, row: &[Cell]) -> fmt::Result {
+    let cells: Vec<_> = row
+        .iter()
+        .map(|cell| {
+            let text = escape(&cell.text);
+            match cell.align {
+                Align::Left => text,
+                Align::Center => format!("style=\"text-align: center;\" | {}", text),
+                Align::Right => format!("style=\"text-align: right;\" | {}", text),
+            }
+        })
+        .collect();
+    let separator = format!(" {}{} ", marker, marker);
+    writeln!(out, "{} {}", marker, cells.join(&separator))
+}
+
+fn escape(text: &SmartString) -> String {
+    let lines: Vec<_> = text
+        .lines()
+        .iter()
+        .map(|line| line.plain().replace('|', "&#124;").replace('!', "&#33;"))
+        .collect();
+    lines.join("<br />")
+}
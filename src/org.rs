@@ -0,0 +1,108 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{Align, AsciiTable, Cell, SmartString, DEFAULT_COLUMN};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_org(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        let plain = |row: Vec<SmartString>| -> Vec<SmartString> {
+            row.iter()
+                .map(|text| {
+                    let lines: Vec<_> = text.lines().iter().map(|l| l.plain()).collect();
+                    SmartString::from(lines.join(" ").replace('|', "\\vert{}"))
+                })
+                .collect()
+        };
+        let header = plain(self.stringify_header(num_cols));
+        let has_header = header.iter().any(|text| !text.is_empty());
+        let body: Vec<_> = rows
+            .map(|row| self.label_row(plain(row), |_| None, None))
+            .collect();
+        let footer: Vec<_> = self
+            .stringify_footer(num_cols)
+            .into_iter()
+            .map(|row| self.footer_row(plain(row)))
+            .collect();
+        let header = if has_header {
+            vec![self.header_row(header)]
+        } else {
+            Vec::new()
+        };
+
+        // Org mode aligns columns by itself, so alignment is only kept through
+        // `<l>`, `<c>` and `<r>` cookies when a column is not left aligned.
+        let aligns: Vec<_> = (0..num_cols).map(|a| self.column_align(a)).collect();
+        let cookies = if aligns.iter().all(|&align| align == Align::Left) {
+            Vec::new()
+        } else {
+            let row = aligns
+                .iter()
+                .map(|align| SmartString::from(format!("<{}>", align.letter())))
+                .collect();
+            vec![self.label_row(row, |_| None, Some(Align::Left))]
+        };
+
+        let cells: Vec<_> = header
+            .iter()
+            .chain(&cookies)
+            .chain(&body)
+            .chain(&footer)
+            .flatten()
+            .collect();
+        let widths = self.content_widths(&cells, num_cols, false);
+        let segments: Vec<_> = widths.iter().map(|w| "-".repeat(w + 2)).collect();
+        let rule = format!("|{}|\n", segments.join("+"));
+        let default_conf = &DEFAULT_COLUMN;
+        let write_row = |out: &mut dyn fmt::Write, row: &[Cell]| -> fmt::Result {
+            let cells: Vec<_> = row
+                .iter()
+                .map(|cell| {
+                    let conf = self.columns.get(&cell.column).unwrap_or(default_conf);
+                    self.format_cell(&cell.text, widths[cell.column], ' ', cell.align, conf)
+                        .to_string()
+                })
+                .collect();
+            writeln!(out, "| {} |", cells.join(" | "))
+        };
+
+        for row in header.iter().chain(&cookies) {
+            write_row(out, row)?;
+        }
+        if has_header {
+            out.write_str(&rule)?;
+        }
+        for row in &body {
+            write_row(out, row)?;
+        }
+        if !footer.is_empty() {
+            out.write_str(&rule)?;
+        }
+        for row in &footer {
+            write_row(out, row)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,582 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::AsciiTable;
+use serde::ser::{self, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::io;
+
+impl AsciiTable {
+    /// Prints one row per record, with the field names as headers.
+    ///
+    /// Nested structs and maps become dotted columns such as `address.city`, while sequences
+    /// are summarized in a single cell. Headers already set on `columns` are kept. Nothing is
+    /// printed if a record fails to serialize, and the error is returned instead.
+    pub fn print_records<T: Serialize>(&self, data: &[T]) -> Result<(), SerializeError> {
+        let (config, rows) = self.records(data)?;
+        config.print(rows);
+        Ok(())
+    }
+
+    /// Like [`print_records`](Self::print_records), but returns the table as a `String`.
+    pub fn format_records<T: Serialize>(&self, data: &[T]) -> Result<String, SerializeError> {
+        let (config, rows) = self.records(data)?;
+        Ok(config.format(rows))
+    }
+
+    /// Like [`print_records`](Self::print_records), but writes the table to `writer`.
+    ///
+    /// A record that fails to serialize gives an error of kind `InvalidData`.
+    pub fn write_records<W: io::Write, T: Serialize>(
+        &self,
+        writer: W,
+        data: &[T],
+    ) -> io::Result<()> {
+        let (config, rows) = self
+            .records(data)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        config.write_to(writer, rows)
+    }
+
+    fn records<T: Serialize>(
+        &self,
+        data: &[T],
+    ) -> Result<(AsciiTable, Vec<Vec<String>>), SerializeError> {
+        let values = data
+            .iter()
+            .map(|record| record.serialize(ValueSerializer))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut tree = KeyTree::default();
+        for value in &values {
+            tree.insert(value, true);
+        }
+        let mut keys = Vec::new();
+        tree.collect(String::new(), &mut keys);
+
+        let index: HashMap<&str, usize> = keys
+            .iter()
+            .enumerate()
+            .map(|(a, key)| (key.as_str(), a))
+            .collect();
+        let rows = values
+            .into_iter()
+            .map(|value| {
+                let mut cells = Vec::new();
+                flatten(value, String::new(), &mut cells);
+                let mut row = vec![String::new(); keys.len()];
+                for (key, text) in cells {
+                    row[index[key.as_str()]] = text;
+                }
+                row
+            })
+            .collect();
+
+        let mut config = self.clone();
+        for (a, key) in keys.into_iter().enumerate() {
+            let column = config.columns.entry(a).or_default();
+            if column.header.is_empty() {
+                column.header = key;
+            }
+        }
+        Ok((config, rows))
+    }
+}
+
+enum Value {
+    None,
+    Scalar(String),
+    Seq(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+// Maps become dotted columns. A sequence at the top level gives one column per
+// element, and nested sequences are summarized in a single cell. `None` gives
+// no cell, so its columns are left blank.
+fn flatten(value: Value, prefix: String, cells: &mut Vec<(String, String)>) {
+    match value {
+        Value::None => {}
+        Value::Map(entries) => {
+            for (name, value) in entries {
+                flatten(value, join(&prefix, &name), cells);
+            }
+        }
+        Value::Seq(items) if prefix.is_empty() => {
+            for (a, value) in items.into_iter().enumerate() {
+                flatten(value, a.to_string(), cells);
+            }
+        }
+        value => cells.push((prefix, summary(&value))),
+    }
+}
+
+// The column keys of all records, merged in field order. A `None` holds the
+// place of its field, which becomes the dotted fields inside it as soon as a
+// record has them.
+#[derive(Default)]
+struct KeyTree {
+    leaf: bool,
+    children: Vec<(String, KeyTree)>,
+}
+
+impl KeyTree {
+    fn insert(&mut self, value: &Value, top: bool) {
+        let entries: Vec<(String, &Value)> = match value {
+            Value::None => return,
+            Value::Map(entries) => entries
+                .iter()
+                .map(|(name, value)| (name.clone(), value))
+                .collect(),
+            Value::Seq(items) if top => items
+                .iter()
+                .enumerate()
+                .map(|(a, value)| (a.to_string(), value))
+                .collect(),
+            _ => {
+                self.leaf = true;
+                return;
+            }
+        };
+        // New keys go after the previous key of the same record. Records
+        // usually repeat the same order, so the next child is checked first.
+        let mut position = 0;
+        for (name, value) in entries {
+            let a = if self
+                .children
+                .get(position)
+                .is_some_and(|(key, _)| *key == name)
+            {
+                position
+            } else if let Some(a) = self.children.iter().position(|(key, _)| *key == name) {
+                a
+            } else {
+                self.children.insert(position, (name, KeyTree::default()));
+                position
+            };
+            self.children[a].1.insert(value, false);
+            position = a + 1;
+        }
+    }
+
+    fn collect(&self, prefix: String, keys: &mut Vec<String>) {
+        if self.leaf {
+            keys.push(prefix.clone());
+        }
+        for (name, child) in &self.children {
+            let key = join(&prefix, name);
+            if child.children.is_empty() && !child.leaf {
+                keys.push(key);
+            } else {
+                child.collect(key, keys);
+            }
+        }
+    }
+}
+
+fn summary(value: &Value) -> String {
+    match value {
+        Value::None => String::new(),
+        Value::Scalar(text) => text.clone(),
+        Value::Seq(items) => {
+            let items: Vec<_> = items.iter().map(summary).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Map(entries) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, summary(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Error returned when a record cannot be serialized into table cells.
+#[derive(Debug)]
+pub struct SerializeError(String);
+
+impl Display for SerializeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerializeError(msg.to_string())
+    }
+}
+
+struct ValueSerializer;
+
+fn scalar<T: Display>(value: T) -> Result<Value, SerializeError> {
+    Ok(Value::Scalar(value.to_string()))
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerializeError;
+    type SerializeSeq = SeqBuilder;
+    type SerializeTuple = SeqBuilder;
+    type SerializeTupleStruct = SeqBuilder;
+    type SerializeTupleVariant = SeqBuilder;
+    type SerializeMap = MapBuilder;
+    type SerializeStruct = MapBuilder;
+    type SerializeStructVariant = MapBuilder;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerializeError> {
+        scalar(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerializeError> {
+        Ok(Value::Seq(
+            v.iter()
+                .map(|byte| Value::Scalar(byte.to_string()))
+                .collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Value, SerializeError> {
+        Ok(Value::None)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerializeError> {
+        scalar("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerializeError> {
+        scalar("")
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerializeError> {
+        scalar(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, SerializeError> {
+        Ok(Value::Map(vec![(
+            variant.to_string(),
+            value.serialize(self)?,
+        )]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqBuilder, SerializeError> {
+        Ok(SeqBuilder {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqBuilder, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, SerializeError> {
+        Ok(SeqBuilder {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapBuilder, SerializeError> {
+        Ok(MapBuilder {
+            variant: None,
+            entries: Vec::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapBuilder, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapBuilder, SerializeError> {
+        Ok(MapBuilder {
+            variant: Some(variant),
+            entries: Vec::new(),
+            key: None,
+        })
+    }
+}
+
+// Variants of an enum are wrapped in a map keyed by the variant name.
+fn wrap(variant: Option<&'static str>, value: Value) -> Value {
+    match variant {
+        Some(variant) => Value::Map(vec![(variant.to_string(), value)]),
+        None => value,
+    }
+}
+
+struct SeqBuilder {
+    variant: Option<&'static str>,
+    items: Vec<Value>,
+}
+
+impl SeqBuilder {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, SerializeError> {
+        Ok(wrap(self.variant, Value::Seq(self.items)))
+    }
+}
+
+impl ser::SerializeSeq for SeqBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+struct MapBuilder {
+    variant: Option<&'static str>,
+    entries: Vec<(String, Value)>,
+    key: Option<String>,
+}
+
+impl MapBuilder {
+    fn push<T: ?Sized + Serialize>(
+        &mut self,
+        key: String,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.entries.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, SerializeError> {
+        Ok(wrap(self.variant, Value::Map(self.entries)))
+    }
+}
+
+impl ser::SerializeMap for MapBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), SerializeError> {
+        self.key = Some(summary(&key.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self.key.take().unwrap_or_default();
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapBuilder {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{AsciiTable, Cell, SmartString};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_roff(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        writeln!(out, ".TS")?;
+        let header = self.stringify_header(num_cols);
+        let header = if header.iter().any(|text| !text.is_empty()) {
+            Some(self.header_row(header))
+        } else {
+            None
+        };
+        if let Some(header) = &header {
+            let formats: Vec<_> = header
+                .iter()
+                .map(|cell| format!("{}b", cell.align.letter()))
+                .collect();
+            writeln!(out, "{}", formats.join(" "))?;
+        }
+        let formats: Vec<_> = (0..num_cols)
+            .map(|a| self.column_align(a).letter().to_string())
+            .collect();
+        writeln!(out, "{}.", formats.join(" "))?;
+        if let Some(header) = &header {
+            write_row(out, header)?;
+            writeln!(out, "_")?;
+        }
+        for row in rows {
+            write_row(out, &self.label_row(row, |_| None, None))?;
+        }
+        let footer = self.stringify_footer(num_cols);
+        if !footer.is_empty() {
+            writeln!(out, "_")?;
+        }
+        for row in footer {
+            write_row(out, &self.footer_row(row))?;
+        }
+        writeln!(out, ".TE")
+    }
+}
+
+fn write_row(out: &mut dyn fmt::Write, row: &[Cell]) -> fmt::Result {
+    let cells: Vec<_> = row.iter().map(|cell| escape(&cell.text)).collect();
+    writeln!(out, "{}", cells.join("\t"))
+}
+
+// Multi-line cells are written as `T{`/`T}` text blocks with `.br` between
+// the lines.
+fn escape(text: &SmartString) -> String {
+    let lines: Vec<_> = text
+        .lines()
+        .iter()
+        .map(|line| escape_line(&line.plain()))
+        .collect();
+    match lines.len() {
+        1 => lines.concat(),
+        _ => format!("T{{\n{}\nT}}", lines.join("\n.br\n")),
+    }
+}
+
+// Backslashes are the roff escape character, and text that starts like a
+// request or reads as a tbl rule or text block delimiter is guarded with the
+// `\&` zero-width escape.
+fn escape_line(text: &str) -> String {
+    let result = text.replace('\\', "\\e").replace('\t', " ");
+    let block = result == "T{" || result == "T}";
+    if result.starts_with(['.', '\'']) || result == "_" || result == "=" || block {
+        format!("\\&{}", result)
+    } else {
+        result
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{AsciiTable, Cell, SmartString, DEFAULT_COLUMN};
+use std::fmt;
+
+impl AsciiTable {
+    pub(crate) fn write_rst(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+        grid: bool,
+    ) -> fmt::Result {
+        if num_cols == 0 {
+            return Ok(());
+        }
+        let plain = |row: Vec<SmartString>| -> Vec<SmartString> {
+            row.iter()
+                .enumerate()
+                .map(|(a, text)| {
+                    if grid {
+                        return SmartString::from(text.plain());
+                    }
+                    let lines: Vec<_> = text.lines().iter().map(|l| l.plain()).collect();
+                    match lines.join(" ") {
+                        // A blank first column would continue the previous row.
+                        text if a == 0 && text.trim().is_empty() => SmartString::from(".."),
+                        // Text that looks like a rule is escaped so it stays a cell.
+                        text if is_rule(&text) => SmartString::from(format!("\\{}", text)),
+                        text => SmartString::from(text),
+                    }
+                })
+                .collect()
+        };
+        let header = self.stringify_header(num_cols);
+        let header = if header.iter().any(|text| !text.is_empty()) {
+            Some(self.header_row(plain(header)))
+        } else {
+            None
+        };
+        let body: Vec<_> = rows
+            .chain(self.stringify_footer(num_cols))
+            .map(|row| self.label_row(plain(row), |_| None, None))
+            .collect();
+        let cells: Vec<_> = header.iter().chain(&body).flatten().collect();
+        let widths: Vec<_> = self
+            .content_widths(&cells, num_cols, false)
+            .into_iter()
+            .map(|width| width.max(1))
+            .collect();
+
+        if grid {
+            let rule = |line: &str| {
+                let segments: Vec<_> = widths.iter().map(|w| line.repeat(w + 2)).collect();
+                format!("+{}+\n", segments.join("+"))
+            };
+            out.write_str(&rule("-"))?;
+            if let Some(header) = &header {
+                self.write_rst_row(out, header, &widths, "| ", " | ", " |")?;
+                out.write_str(&rule("="))?;
+            }
+            for row in &body {
+                self.write_rst_row(out, row, &widths, "| ", " | ", " |")?;
+                out.write_str(&rule("-"))?;
+            }
+        } else {
+            let segments: Vec<_> = widths.iter().map(|&w| "=".repeat(w)).collect();
+            let rule = format!("{}\n", segments.join("  "));
+            out.write_str(&rule)?;
+            if let Some(header) = &header {
+                self.write_rst_row(out, header, &widths, "", "  ", "")?;
+                out.write_str(&rule)?;
+            }
+            for row in &body {
+                self.write_rst_row(out, row, &widths, "", "  ", "")?;
+            }
+            out.write_str(&rule)?;
+        }
+        Ok(())
+    }
+
+    fn write_rst_row(
+        &self,
+        out: &mut dyn fmt::Write,
+        row: &[Cell],
+        widths: &[usize],
+        first: &str,
+        inner: &str,
+        last: &str,
+    ) -> fmt::Result {
+        let default_conf = &DEFAULT_COLUMN;
+        let lines: Vec<_> = row.iter().map(|cell| cell.text.lines()).collect();
+        let height = lines.iter().map(|lines| lines.len()).max().unwrap_or(1);
+        for a in 0..height {
+            let cells: Vec<_> = row
+                .iter()
+                .zip(&lines)
+                .map(|(cell, lines)| {
+                    let conf = self.columns.get(&cell.column).unwrap_or(default_conf);
+                    let text = lines.get(a).cloned().unwrap_or_else(SmartString::new);
+                    self.format_cell(&text, widths[cell.column], ' ', cell.align, conf)
+                        .to_string()
+                })
+                .collect();
+            let line = format!("{}{}{}", first, cells.join(inner), last);
+            writeln!(out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn is_rule(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && (text.chars().all(|ch| ch == '=' || ch == ' ')
+            || text.chars().all(|ch| ch == '-' || ch == ' '))
+}
@@ -0,0 +1,130 @@
+// Copyright 2019-2020 Gerrit Viljoen
+
+// This file is part of ascii-table.
+//
+// ascii-table is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ascii-table is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ascii-table.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::ansi::{self, Style};
+use crate::{AsciiTable, SmartString};
+use std::fmt;
+use unicode_width::UnicodeWidthChar;
+
+const FONT_SIZE: usize = 15;
+const CHAR_WIDTH: usize = 9;
+const LINE_HEIGHT: usize = 18;
+
+impl AsciiTable {
+    pub(crate) fn write_svg(
+        &self,
+        out: &mut dyn fmt::Write,
+        num_cols: usize,
+        rows: &mut dyn Iterator<Item = Vec<SmartString>>,
+    ) -> fmt::Result {
+        let mut table = String::new();
+        self.write_table(rows.collect(), num_cols, &mut table)?;
+        let lines: Vec<_> = table.lines().map(SmartString::from).collect();
+        let width = lines
+            .iter()
+            .map(|line| columns(&line.plain()))
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             font-family=\"monospace\" font-size=\"{}\">",
+            width * CHAR_WIDTH,
+            lines.len() * LINE_HEIGHT,
+            FONT_SIZE
+        )?;
+        for (a, line) in lines.iter().enumerate() {
+            write_line(out, line, a * LINE_HEIGHT)?;
+        }
+        writeln!(out, "</svg>")
+    }
+}
+
+// Every run of text is placed at its terminal column, so wide characters and
+// fonts with slightly different advances keep the columns lined up.
+fn write_line(out: &mut dyn fmt::Write, line: &SmartString, top: usize) -> fmt::Result {
+    let runs: Vec<_> = ansi::runs(line)
+        .into_iter()
+        .scan(0, |column, (style, text)| {
+            let x = *column * CHAR_WIDTH;
+            *column += columns(text);
+            Some((x, style, text))
+        })
+        .collect();
+    for (x, style, text) in &runs {
+        if let Some(color) = style.background {
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                x,
+                top,
+                columns(text) * CHAR_WIDTH,
+                LINE_HEIGHT,
+                ansi::hex(color)
+            )?;
+        }
+    }
+    write!(
+        out,
+        "<text y=\"{}\" xml:space=\"preserve\">",
+        top + FONT_SIZE
+    )?;
+    for (x, style, text) in &runs {
+        write!(
+            out,
+            "<tspan x=\"{}\"{}>{}</tspan>",
+            x,
+            attributes(style),
+            escape(text)
+        )?;
+    }
+    writeln!(out, "</text>")
+}
+
+// Terminal columns taken by the text. Box drawing characters are ambiguous
+// width, but are drawn one column wide like in the table layout's borders.
+fn columns(text: &str) -> usize {
+    text.chars()
+        .map(|ch| match ch {
+            '\u{2500}'..='\u{257f}' => 1,
+            _ => ch.width_cjk().unwrap_or(0),
+        })
+        .sum()
+}
+
+fn attributes(style: &Style) -> String {
+    let mut result = String::new();
+    if let Some(color) = style.foreground {
+        result.push_str(&format!(" fill=\"{}\"", ansi::hex(color)));
+    }
+    if style.bold {
+        result.push_str(" font-weight=\"bold\"");
+    }
+    if style.italic {
+        result.push_str(" font-style=\"italic\"");
+    }
+    if style.underline {
+        result.push_str(" text-decoration=\"underline\"");
+    }
+    result
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
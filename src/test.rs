@@ -18,7 +18,7 @@
 use colorful::Color;
 use colorful::Colorful;
 
-use crate::{AsciiTable, Column};
+use crate::{AsciiTable, BorderStyle, Column, ColumnGroup, Output, Overflow, TitlePosition, Truncation, VerticalAlign};
 use crate::Align::*;
 
 use std::collections::BTreeMap;
@@ -34,14 +34,44 @@ fn cube_config() -> AsciiTable {
 
 #[test]
 fn backwards_compatible() {
-    AsciiTable {
+    let _ = AsciiTable {
         max_width: 0,
+        use_terminal_width: false,
+        default_align: Left,
+        header_align: None,
+        border: BorderStyle::THIN,
+        padding_left: 1,
+        padding_right: 1,
+        indent: 0,
+        separate_rows: false,
+        separators: BTreeMap::new(),
+        col_spans: BTreeMap::new(),
+        row_spans: BTreeMap::new(),
+        column_groups: BTreeMap::new(),
+        footer: Vec::new(),
+        footer_align: None,
+        footer_separator: None,
+        title: String::new(),
+        title_align: Left,
+        title_position: TitlePosition::Border,
+        caption: String::new(),
+        caption_align: Left,
+        output: Output::Table,
         columns: BTreeMap::new()
     };
-    Column {
+    let _ = Column {
         header: String::new(),
         align: Left,
-        max_width: 0
+        vertical_align: VerticalAlign::Top,
+        header_align: None,
+        footer_align: None,
+        max_width: 0,
+        width: None,
+        overflow: Overflow::Truncate,
+        truncation: Truncation::End,
+        truncation_marker: "+".into(),
+        padding_left: None,
+        padding_right: None
     };
 }
 
@@ -478,7 +508,7 @@ fn align_right() {
 
     let input = vec![&[1], &[23], &[456]];
     let expected = "┌─────┐\n\
-                    │ a   │\n\
+                    │   a │\n\
                     ├─────┤\n\
                     │   1 │\n\
                     │  23 │\n\
@@ -495,7 +525,7 @@ fn align_center() {
 
     let input = vec![&[1], &[23], &[456], &[7890], &[12345]];
     let expected = "┌───────┐\n\
-                    │ a     │\n\
+                    │   a   │\n\
                     ├───────┤\n\
                     │   1   │\n\
                     │  23   │\n\
@@ -689,3 +719,1657 @@ fn color_codes_trunc() {
 
     assert_eq!(expected, config.format(input));
 }
+
+#[test]
+fn border_ascii() {
+    let mut config = cube_config();
+    config.border = BorderStyle::ASCII;
+    let input = vec![&[1, 2, 3], &[4, 5, 6]];
+    let expected = "+---+---+---+\n\
+                    | a | b | c |\n\
+                    +---+---+---+\n\
+                    | 1 | 2 | 3 |\n\
+                    | 4 | 5 | 6 |\n\
+                    +---+---+---+\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn border_double() {
+    let mut config = cube_config();
+    config.border = BorderStyle::DOUBLE;
+    let input = vec![&[1, 2, 3]];
+    let expected = "╔═══╦═══╦═══╗\n\
+                    ║ a ║ b ║ c ║\n\
+                    ╠═══╬═══╬═══╣\n\
+                    ║ 1 ║ 2 ║ 3 ║\n\
+                    ╚═══╩═══╩═══╝\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn border_rounded() {
+    let config = AsciiTable {
+        border: BorderStyle::ROUNDED,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2]];
+    let expected = "╭───┬───╮\n\
+                    │ 1 │ 2 │\n\
+                    ╰───┴───╯\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn border_borderless() {
+    let mut config = cube_config();
+    config.border = BorderStyle::BORDERLESS;
+    let input = vec![&[1, 2, 3], &[4, 5, 6]];
+    let expected = " a  b  c \n\
+                    \x201  2  3 \n\
+                    \x204  5  6 \n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn border_borderless_width() {
+    let config = AsciiTable {
+        max_width: 5,
+        border: BorderStyle::BORDERLESS,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[12345]];
+    let expected = " 12+ \n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn wrap() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 4, overflow: Overflow::Wrap, ..Column::default()});
+    let input = vec![&["abcdefghij", "x"]];
+    let expected = "┌──────┬───┐\n\
+                    │ abcd │ x │\n\
+                    │ efgh │   │\n\
+                    │ ij   │   │\n\
+                    └──────┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn wrap_words() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 10, overflow: Overflow::WrapWords, ..Column::default()});
+    let input = vec![&["the quick brown fox jumps"], &["over"]];
+    let expected = "┌────────────┐\n\
+                    │ the quick  │\n\
+                    │ brown fox  │\n\
+                    │ jumps      │\n\
+                    │ over       │\n\
+                    └────────────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn wrap_words_long_word() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 5, overflow: Overflow::WrapWords, ..Column::default()});
+    let input = vec![&["a abcdefghijk b"]];
+    let expected = "┌───────┐\n\
+                    │ a     │\n\
+                    │ abcde │\n\
+                    │ fghij │\n\
+                    │ k b   │\n\
+                    └───────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn wrap_header() {
+    let mut config = AsciiTable {
+        max_width: 9,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "long header".to_string(), overflow: Overflow::WrapWords, ..Column::default()});
+    let input = vec![&[1]];
+    let expected = "┌───────┐\n\
+                    │ long  │\n\
+                    │ heade │\n\
+                    │ r     │\n\
+                    ├───────┤\n\
+                    │ 1     │\n\
+                    └───────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn wrap_cjk() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 5, overflow: Overflow::Wrap, ..Column::default()});
+    let input = vec![&["漢字漢字"]];
+    let expected = "┌───────┐\n\
+                    │ 漢字  │\n\
+                    │ 漢字  │\n\
+                    └───────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn color_codes_wrap() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 3, overflow: Overflow::Wrap, ..Column::default()});
+    let input = vec![
+        vec!["Hello".color(Color::Blue).bg_color(Color::Yellow).bold()]
+    ];
+    let expected = "┌─────┐\n\
+                    │ \u{1b}[38;5;4m\u{1b}[48;5;3;1mHel\u{1b}[0m │\n\
+                    │ \u{1b}[38;5;4m\u{1b}[48;5;3;1mlo \u{1b}[0m │\n\
+                    └─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn multiline_cell() {
+    let config = cube_config();
+    let input = vec![vec!["1", "first\nsecond", "3"], vec!["4", "5", "6"]];
+    let expected = "┌───┬────────┬───┐\n\
+                    │ a │ b      │ c │\n\
+                    ├───┼────────┼───┤\n\
+                    │ 1 │ first  │ 3 │\n\
+                    │   │ second │   │\n\
+                    │ 4 │ 5      │ 6 │\n\
+                    └───┴────────┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn multiline_cell_crlf() {
+    let config = AsciiTable::default();
+    let input = vec![vec!["ab\r\nc", "d\ne\nf"]];
+    let expected = "┌────┬───┐\n\
+                    │ ab │ d │\n\
+                    │ c  │ e │\n\
+                    │    │ f │\n\
+                    └────┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn multiline_header() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {header: "total\nUSD".to_string(), align: Right, ..Column::default()});
+    let input = vec![&[12]];
+    let expected = "┌───────┐\n\
+                    │ total │\n\
+                    │   USD │\n\
+                    ├───────┤\n\
+                    │    12 │\n\
+                    └───────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn multiline_cell_truncate() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 4, ..Column::default()});
+    let input = vec![&["abcdef\nab"]];
+    let expected = "┌──────┐\n\
+                    │ abc+ │\n\
+                    │ ab   │\n\
+                    └──────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn color_codes_multiline() {
+    let config = AsciiTable::default();
+    let input = vec![
+        vec!["ab\ncde".color(Color::Blue).bold()]
+    ];
+    let expected = "┌─────┐\n\
+                    │ \u{1b}[38;5;4;1mab \u{1b}[0m │\n\
+                    │ \u{1b}[38;5;4;1mcde\u{1b}[0m │\n\
+                    └─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn truncation_marker_ellipsis() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 4, truncation_marker: "…".into(), ..Column::default()});
+    let input = vec![&["abcdef"]];
+    let expected = "┌──────┐\n\
+                    │ ab… │\n\
+                    └──────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn truncation_marker_dots() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 5, truncation_marker: "...".into(), ..Column::default()});
+    config.columns.insert(1, Column {max_width: 2, truncation_marker: "...".into(), ..Column::default()});
+    let input = vec![&["abcdefgh", "abcdefgh"]];
+    let expected = "┌───────┬────┐\n\
+                    │ ab... │ .. │\n\
+                    └───────┴────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn truncation_marker_owned() {
+    let marker = "~".repeat(2);
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 4, truncation_marker: marker.into(), ..Column::default()});
+    let input = vec![&["abcdef"]];
+    let expected = "┌──────┐\n\
+                    │ ab~~ │\n\
+                    └──────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn truncation_start() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 6, truncation: Truncation::Start, truncation_marker: "..".into(), ..Column::default()});
+    let input = vec![&["/usr/local/bin"]];
+    let expected = "┌────────┐\n\
+                    │ ../bin │\n\
+                    └────────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn truncation_middle() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 8, truncation: Truncation::Middle, truncation_marker: "...".into(), ..Column::default()});
+    let input = vec![&["0123456789abcdef"]];
+    let expected = "┌──────────┐\n\
+                    │ 012...ef │\n\
+                    └──────────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn truncation_cjk() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 4, ..Column::default()});
+    let input = vec![&["漢字漢字"]];
+    let expected = "┌──────┐\n\
+                    │ 漢+  │\n\
+                    └──────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn color_codes_truncation_middle() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {max_width: 3, truncation: Truncation::Middle, ..Column::default()});
+    let input = vec![
+        vec!["Hello".color(Color::Blue).bg_color(Color::Yellow).bold()]
+    ];
+    let expected = "┌─────┐\n\
+                    │ \u{1b}[38;5;4m\u{1b}[48;5;3;1mH+o\u{1b}[0m │\n\
+                    └─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn no_padding() {
+    let config = AsciiTable {
+        padding_left: 0,
+        padding_right: 0,
+        ..cube_config()
+    };
+    let input = vec![&[1, 2, 3]];
+    let expected = "┌─┬─┬─┐\n\
+                    │a│b│c│\n\
+                    ├─┼─┼─┤\n\
+                    │1│2│3│\n\
+                    └─┴─┴─┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn column_padding() {
+    let mut config = cube_config();
+    config.columns.get_mut(&1).unwrap().padding_left = Some(3);
+    config.columns.get_mut(&2).unwrap().padding_right = Some(0);
+    let input = vec![&[1, 2, 3]];
+    let expected = "┌───┬─────┬──┐\n\
+                    │ a │   b │ c│\n\
+                    ├───┼─────┼──┤\n\
+                    │ 1 │   2 │ 3│\n\
+                    └───┴─────┴──┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn indent() {
+    let config = AsciiTable {
+        indent: 4,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2]];
+    let expected = "    ┌───┬───┐\n    \
+                    │ 1 │ 2 │\n    \
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn padding_max_width() {
+    let config = AsciiTable {
+        max_width: 12,
+        padding_left: 2,
+        padding_right: 2,
+        indent: 2,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[12345]];
+    let expected = "  ┌────────┐\n  \
+                    │  123+  │\n  \
+                    └────────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn padding_too_wide() {
+    let config = AsciiTable {
+        max_width: 8,
+        padding_left: 2,
+        padding_right: 2,
+        indent: 2,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2]];
+    let expected = "  ┌────┐\n  \
+                    │    │\n  \
+                    └────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn header_align() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {header: String::from("a"), align: Right, header_align: Some(Left), ..Column::default()});
+    config.columns.insert(1, Column {header: String::from("b"), header_align: Some(Center), ..Column::default()});
+    let input = vec![&[123, 456]];
+    let expected = "┌─────┬─────┐\n\
+                    │ a   │  b  │\n\
+                    ├─────┼─────┤\n\
+                    │ 123 │ 456 │\n\
+                    └─────┴─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn header_align_table_default() {
+    let mut config = AsciiTable {
+        header_align: Some(Center),
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: String::from("a"), align: Right, ..Column::default()});
+    config.columns.insert(1, Column {header: String::from("b"), header_align: Some(Right), ..Column::default()});
+    let input = vec![&[123, 456]];
+    let expected = "┌─────┬─────┐\n\
+                    │  a  │   b │\n\
+                    ├─────┼─────┤\n\
+                    │ 123 │ 456 │\n\
+                    └─────┴─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn header_align_follows_default_align() {
+    let mut config = AsciiTable::new(Right, 80);
+    config.columns.insert(1, Column {header: String::from("b"), align: Left, ..Column::default()});
+    let input = vec![&[123, 456]];
+    let expected = "┌─────┬─────┐\n\
+                    │     │ b   │\n\
+                    ├─────┼─────┤\n\
+                    │ 123 │ 456 │\n\
+                    └─────┴─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn separate_rows() {
+    let config = AsciiTable {
+        separate_rows: true,
+        ..cube_config()
+    };
+    let input = vec![&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]];
+    let expected = "┌───┬───┬───┐\n\
+                    │ a │ b │ c │\n\
+                    ├───┼───┼───┤\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    ├───┼───┼───┤\n\
+                    │ 4 │ 5 │ 6 │\n\
+                    ├───┼───┼───┤\n\
+                    │ 7 │ 8 │ 9 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn separators() {
+    let mut config = AsciiTable::default();
+    config.separators.insert(1, BorderStyle::HEAVY);
+    config.separators.insert(3, BorderStyle::DOUBLE);
+    let input = vec![&[1, 2], &[3, 4], &[5, 6], &[7, 8]];
+    let expected = "┌───┬───┐\n\
+                    │ 1 │ 2 │\n\
+                    │ 3 │ 4 │\n\
+                    ┣━━━╋━━━┫\n\
+                    │ 5 │ 6 │\n\
+                    │ 7 │ 8 │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn separators_mixed_weight() {
+    let mut config = AsciiTable::default();
+    config.separators.insert(0, BorderStyle::HEAVY_SEPARATOR);
+    config.separators.insert(1, BorderStyle::DOUBLE_SEPARATOR);
+    let input = vec![&[1, 2], &[3, 4], &[5, 6]];
+    let expected = "┌───┬───┐\n\
+                    │ 1 │ 2 │\n\
+                    ┝━━━┿━━━┥\n\
+                    │ 3 │ 4 │\n\
+                    ╞═══╪═══╡\n\
+                    │ 5 │ 6 │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn separators_override_separate_rows() {
+    let mut config = AsciiTable {
+        separate_rows: true,
+        ..AsciiTable::default()
+    };
+    config.separators.insert(0, BorderStyle::DOUBLE);
+    config.separators.insert(1, BorderStyle::BORDERLESS);
+    let input = vec![&[1, 2], &[3, 4], &[5, 6], &[7, 8]];
+    let expected = "┌───┬───┐\n\
+                    │ 1 │ 2 │\n\
+                    ╠═══╬═══╣\n\
+                    │ 3 │ 4 │\n\
+                    │ 5 │ 6 │\n\
+                    ├───┼───┤\n\
+                    │ 7 │ 8 │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn footer() {
+    let mut config = cube_config();
+    config.footer = vec![vec!["12".to_string(), "15".to_string(), "18".to_string()]];
+    let input = vec![&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]];
+    let expected = "┌────┬────┬────┐\n\
+                    │ a  │ b  │ c  │\n\
+                    ├────┼────┼────┤\n\
+                    │ 1  │ 2  │ 3  │\n\
+                    │ 4  │ 5  │ 6  │\n\
+                    │ 7  │ 8  │ 9  │\n\
+                    ├────┼────┼────┤\n\
+                    │ 12 │ 15 │ 18 │\n\
+                    └────┴────┴────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn footer_rows() {
+    let mut config = AsciiTable {
+        footer_separator: Some(BorderStyle::DOUBLE),
+        ..AsciiTable::default()
+    };
+    config.footer = vec![
+        vec!["sum".to_string(), "10".to_string()],
+        vec!["avg".to_string()],
+        vec!["max".to_string(), "4".to_string(), "ignored".to_string()],
+    ];
+    let input = vec![&[1, 2], &[3, 4]];
+    let expected = "┌─────┬────┐\n\
+                    │ 1   │ 2  │\n\
+                    │ 3   │ 4  │\n\
+                    ╠═════╬════╣\n\
+                    │ sum │ 10 │\n\
+                    │ avg │    │\n\
+                    │ max │ 4  │\n\
+                    └─────┴────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn footer_without_data() {
+    let mut config = cube_config();
+    config.footer_separator = Some(BorderStyle::DOUBLE);
+    config.footer = vec![vec!["0".to_string(), "0".to_string(), "0".to_string()]];
+    let input: Vec<Vec<i32>> = vec![];
+    let expected = "┌───┬───┬───┐\n\
+                    │ a │ b │ c │\n\
+                    ╠═══╬═══╬═══╣\n\
+                    │ 0 │ 0 │ 0 │\n\
+                    └───┴───┴───┘\n";
+    let mut output = String::new();
+
+    assert_eq!(expected, config.format(&input));
+    config.fmt_stream(&mut output, &input, 1).unwrap();
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn footer_align() {
+    let mut config = AsciiTable {
+        footer_align: Some(Right),
+        ..AsciiTable::default()
+    };
+    config.columns.insert(1, Column {align: Center, footer_align: Some(Left), ..Column::default()});
+    config.columns.insert(2, Column {align: Center, ..Column::default()});
+    config.footer = vec![vec!["1".to_string(), "2".to_string(), "3".to_string()]];
+    let input = vec![&["abc", "abc", "abc"]];
+    let expected = "┌─────┬─────┬─────┐\n\
+                    │ abc │ abc │ abc │\n\
+                    ├─────┼─────┼─────┤\n\
+                    │   1 │ 2   │   3 │\n\
+                    └─────┴─────┴─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn title() {
+    let config = AsciiTable {
+        title: "Users".to_string(),
+        ..cube_config()
+    };
+    let input = vec![&[1, 2, 3]];
+    let expected = "┌─ Users ───┐\n\
+                    │ a │ b │ c │\n\
+                    ├───┼───┼───┤\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn title_align() {
+    let mut config = AsciiTable {
+        title: "ab".to_string(),
+        title_align: Center,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2, 3]];
+    let expected = "┌─── ab ┬───┐\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input.clone()));
+
+    config.title_align = Right;
+    let expected = "┌───┬── ab ─┐\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn title_above() {
+    let config = AsciiTable {
+        title: "Users".to_string(),
+        title_align: Center,
+        title_position: TitlePosition::Above,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2, 3]];
+    let expected = "    Users\n\
+                    ┌───┬───┬───┐\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn title_truncated() {
+    let config = AsciiTable {
+        title: "Inventory".to_string(),
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2]];
+    let expected = "┌─ In+ ─┐\n\
+                    │ 1 │ 2 │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn title_borderless() {
+    let config = AsciiTable {
+        title: "Users".to_string(),
+        border: BorderStyle::BORDERLESS,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2, 3]];
+    let expected = "Users\n \
+                    1  2  3 \n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn caption() {
+    let config = AsciiTable {
+        caption: "Table 1: digits\nfrom 1 to 3".to_string(),
+        caption_align: Right,
+        indent: 2,
+        ..AsciiTable::default()
+    };
+    let input = vec![&[1, 2, 3]];
+    let expected = "  ┌───┬───┬───┐\n  \
+                    │ 1 │ 2 │ 3 │\n  \
+                    └───┴───┴───┘\n  \
+                    Table 1: dig+\n  \
+                    \x20 from 1 to 3\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn col_span() {
+    let mut config = cube_config();
+    config.col_spans.insert((1, 0), 2);
+    config.col_spans.insert((2, 1), 2);
+    let input = vec![&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]];
+    let expected = "┌───┬───┬───┐\n\
+                    │ a │ b │ c │\n\
+                    ├───┼───┼───┤\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    │ 4     │ 6 │\n\
+                    │ 7 │ 8     │\n\
+                    └───┴───────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn col_span_junctions() {
+    let mut config = AsciiTable {
+        separate_rows: true,
+        ..AsciiTable::default()
+    };
+    config.col_spans.insert((0, 0), 3);
+    config.col_spans.insert((1, 0), 2);
+    let input = vec![&[1, 0, 0], &[4, 0, 6], &[7, 8, 9]];
+    let expected = "┌───────────┐\n\
+                    │ 1         │\n\
+                    ├───────┬───┤\n\
+                    │ 4     │ 6 │\n\
+                    ├───┬───┼───┤\n\
+                    │ 7 │ 8 │ 9 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn col_span_wide_text() {
+    let mut config = AsciiTable::default();
+    config.col_spans.insert((0, 0), 2);
+    let input = vec![vec!["a long cell", ""], vec!["1", "2"]];
+    let expected = "┌─────────────┐\n\
+                    │ a long cell │\n\
+                    │ 1    │ 2    │\n\
+                    └──────┴──────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn row_span() {
+    let mut config = cube_config();
+    config.separate_rows = true;
+    config.row_spans.insert((0, 0), 2);
+    config.row_spans.insert((1, 2), 2);
+    let input = vec![&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]];
+    let expected = "┌───┬───┬───┐\n\
+                    │ a │ b │ c │\n\
+                    ├───┼───┼───┤\n\
+                    │ 1 │ 2 │ 3 │\n\
+                    │   ├───┼───┤\n\
+                    │   │ 5 │ 6 │\n\
+                    ├───┼───┤   │\n\
+                    │ 7 │ 8 │   │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn row_span_with_col_span() {
+    let mut config = AsciiTable::default();
+    config.col_spans.insert((0, 0), 2);
+    config.row_spans.insert((0, 0), 2);
+    let input = vec![&[1, 0, 3], &[0, 0, 6], &[7, 8, 9]];
+    let expected = "┌───────┬───┐\n\
+                    │ 1     │ 3 │\n\
+                    │       │ 6 │\n\
+                    │ 7 │ 8 │ 9 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn row_span_vertical_align() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {vertical_align: VerticalAlign::Middle, ..Column::default()});
+    config.columns.insert(1, Column {vertical_align: VerticalAlign::Bottom, ..Column::default()});
+    config.row_spans.insert((0, 0), 3);
+    config.row_spans.insert((0, 1), 3);
+    let input = vec![vec!["a", "b", "1"], vec!["", "", "2"], vec!["", "", "3"]];
+    let expected = "┌───┬───┬───┐\n\
+                    │   │   │ 1 │\n\
+                    │ a │   │ 2 │\n\
+                    │   │ b │ 3 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn row_span_tall_text() {
+    let mut config = AsciiTable {
+        separate_rows: true,
+        ..AsciiTable::default()
+    };
+    config.row_spans.insert((0, 0), 2);
+    let input = vec![vec!["a\nb\nc\nd", "1"], vec!["", "2"]];
+    let expected = "┌───┬───┐\n\
+                    │ a │ 1 │\n\
+                    │ b ├───┤\n\
+                    │ c │ 2 │\n\
+                    │ d │   │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn row_span_clamped() {
+    let mut config = AsciiTable::default();
+    config.row_spans.insert((1, 0), 5);
+    let input = vec![&[1, 2], &[3, 4]];
+    let expected = "┌───┬───┐\n\
+                    │ 1 │ 2 │\n\
+                    │ 3 │ 4 │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn col_span_clamped() {
+    let mut config = AsciiTable::default();
+    config.col_spans.insert((0, 1), 5);
+    let input = vec![&[1, 2, 3], &[4, 5, 6]];
+    let expected = "┌───┬───────┐\n\
+                    │ 1 │ 2     │\n\
+                    │ 4 │ 5 │ 6 │\n\
+                    └───┴───┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn column_groups() {
+    let mut config = AsciiTable::default();
+    for (a, month) in ["Jan", "Feb", "Mar", "Apr"].iter().enumerate() {
+        config.columns.insert(a + 1, Column::with_header(month));
+    }
+    config.column_groups.insert(1, ColumnGroup::new("Q1", 3));
+    let input = vec![&["x", "1", "2", "3", "4"]];
+    let expected = "┌───┬─────────────────┬─────┐\n\
+                    │   │       Q1        │     │\n\
+                    ├───┼─────┬─────┬─────┼─────┤\n\
+                    │   │ Jan │ Feb │ Mar │ Apr │\n\
+                    ├───┼─────┼─────┼─────┼─────┤\n\
+                    │ x │ 1   │ 2   │ 3   │ 4   │\n\
+                    └───┴─────┴─────┴─────┴─────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn column_groups_wide_header() {
+    let mut config = AsciiTable::default();
+    config.column_groups.insert(0, ColumnGroup {align: Left, ..ColumnGroup::new("First quarter", 2)});
+    let input = vec![&[1, 2]];
+    let expected = "┌───────────────┐\n\
+                    │ First quarter │\n\
+                    ├───────┬───────┤\n\
+                    │ 1     │ 2     │\n\
+                    └───────┴───────┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn write_to() {
+    let config = cube_config();
+    let input = vec![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    let mut output = Vec::new();
+
+    config.write_to(&mut output, &input).unwrap();
+    assert_eq!(config.format(&input).into_bytes(), output);
+}
+
+#[test]
+fn write_to_error() {
+    let config = cube_config();
+    let input = vec![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    let mut output = [0; 20];
+
+    let error = config.write_to(&mut output[..], &input).unwrap_err();
+    assert_eq!(std::io::ErrorKind::WriteZero, error.kind());
+}
+
+#[test]
+fn fmt_to() {
+    let config = cube_config();
+    let input = vec![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    let mut output = String::from("table:\n");
+
+    config.fmt_to(&mut output, &input).unwrap();
+    assert_eq!(format!("table:\n{}", config.format(&input)), output);
+}
+
+#[test]
+fn column_width() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(0, Column {width: Some(4), ..Column::default()});
+    config.columns.insert(1, Column {width: Some(2), ..Column::default()});
+    let input = vec![&[1, 22, 3], &[4, 555, 6]];
+    let expected = "┌──────┬────┬───┐\n\
+                    │ 1    │ 22 │ 3 │\n\
+                    │ 4    │ 5+ │ 6 │\n\
+                    └──────┴────┴───┘\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn write_stream_sample() {
+    let mut config = cube_config();
+    config.footer = vec![vec!["x".into()]];
+    let input = vec![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    let mut output = String::new();
+
+    config.fmt_stream(&mut output, &input, 3).unwrap();
+    assert_eq!(config.format(&input), output);
+}
+
+#[test]
+fn write_stream_without_sample() {
+    let mut config = AsciiTable::default();
+    let input = vec![[1, 22], [333, 4]];
+    let expected = "┌───┬────┐\n\
+                    │ 1 │ 22 │\n\
+                    │ + │ 4  │\n\
+                    └───┴────┘\n";
+    let mut output = String::new();
+
+    config.fmt_stream(&mut output, &input, 0).unwrap();
+    assert_eq!(expected, output);
+
+    config.columns.insert(1, Column {header: "b".into(), ..Column::default()});
+    let expected = "┌───┬────┐\n\
+                    │   │ b  │\n\
+                    ├───┼────┤\n\
+                    │ 1 │ 22 │\n\
+                    │ + │ 4  │\n\
+                    └───┴────┘\n";
+    let mut output = String::new();
+
+    config.fmt_stream(&mut output, &input, 0).unwrap();
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn write_stream_header_only_column() {
+    let mut config = AsciiTable::default();
+    config.columns.insert(2, Column {header: "c".into(), ..Column::default()});
+    let input = vec![[1, 2], [3, 4]];
+    let mut output = String::new();
+
+    config.fmt_stream(&mut output, &input, 1).unwrap();
+    assert_eq!(config.format(&input), output);
+}
+
+#[test]
+fn write_stream_fixed_layout() {
+    let mut config = AsciiTable {
+        separate_rows: true,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "id".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "message".into(), width: Some(7), overflow: Overflow::Wrap, ..Column::default()});
+    let input = vec![vec!["1", "started"], vec!["22", "connection lost"], vec!["333", "extra", "cell"]];
+    let expected = "┌────┬─────────┐\n\
+                    │ id │ message │\n\
+                    ├────┼─────────┤\n\
+                    │ 1  │ started │\n\
+                    ├────┼─────────┤\n\
+                    │ 22 │ connect │\n\
+                    │    │ ion los │\n\
+                    │    │ t       │\n\
+                    ├────┼─────────┤\n\
+                    │ 3+ │ extra   │\n\
+                    └────┴─────────┘\n";
+    let mut output = Vec::new();
+
+    config.write_stream(&mut output, input, 1).unwrap();
+    assert_eq!(expected, String::from_utf8(output).unwrap());
+}
+
+#[test]
+fn write_stream_empty() {
+    let config = cube_config();
+    let input: Vec<Vec<i32>> = vec![];
+    let mut output = String::new();
+
+    config.fmt_stream(&mut output, &input, 10).unwrap();
+    assert_eq!(config.format(&input), output);
+}
+
+#[test]
+fn use_terminal_width() {
+    let config = AsciiTable {
+        max_width: 12,
+        use_terminal_width: true,
+        ..AsciiTable::default()
+    };
+
+    assert_eq!(40, config.width_limit(Some(40)));
+    assert_eq!(12, config.width_limit(None));
+}
+
+#[test]
+fn use_terminal_width_disabled() {
+    let config = AsciiTable {
+        max_width: 12,
+        ..AsciiTable::default()
+    };
+
+    assert_eq!(12, config.width_limit(Some(40)));
+}
+
+#[test]
+fn markdown() {
+    let mut config = AsciiTable {
+        output: Output::Markdown,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Mode".into(), align: Center, ..Column::default()});
+    config.columns.insert(2, Column {header: "Size".into(), align: Right, max_width: 2, ..Column::default()});
+    config.footer = vec![vec!["total".into(), "".into(), "1234".into()]];
+    let input = vec![vec!["a|b", "rw", "1000"], vec!["two\nlines", "r"]];
+    let expected = "| Name | Mode | Size |\n\
+                    | :--- | :---: | ---: |\n\
+                    | a\\|b | rw | 1000 |\n\
+                    | two<br>lines | r |  |\n\
+                    | total |  | 1234 |\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn markdown_header_only() {
+    let mut config = AsciiTable {
+        output: Output::Markdown,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "a".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "b".into(), ..Column::default()});
+    let input: Vec<Vec<i32>> = vec![];
+    let expected = "| a | b |\n\
+                    | :--- | :--- |\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn markdown_color_codes() {
+    let config = AsciiTable {
+        output: Output::Markdown,
+        ..AsciiTable::default()
+    };
+    let text = "hello".color(Color::Blue).bold();
+    let input = vec![vec![text]];
+    let expected = "|  |\n\
+                    | :--- |\n\
+                    | hello |\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn html() {
+    let mut config = AsciiTable {
+        output: Output::Html,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Size".into(), align: Right, header_align: Some(Center), ..Column::default()});
+    config.footer = vec![vec!["total".into(), "3".into()]];
+    let input = vec![vec!["<a> & \"b\"", "1"], vec!["two\nlines", "2"]];
+    let expected = "<table>\n\
+                    <thead>\n\
+                    <tr><th style=\"text-align: left\">Name</th><th style=\"text-align: center\">Size</th></tr>\n\
+                    </thead>\n\
+                    <tbody>\n\
+                    <tr><td style=\"text-align: left\">&lt;a&gt; &amp; &quot;b&quot;</td><td style=\"text-align: right\">1</td></tr>\n\
+                    <tr><td style=\"text-align: left\">two<br>lines</td><td style=\"text-align: right\">2</td></tr>\n\
+                    </tbody>\n\
+                    <tfoot>\n\
+                    <tr><td style=\"text-align: left\">total</td><td style=\"text-align: right\">3</td></tr>\n\
+                    </tfoot>\n\
+                    </table>\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn html_header_only() {
+    let mut config = AsciiTable {
+        output: Output::Html,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "a".into(), ..Column::default()});
+    let input: Vec<Vec<i32>> = vec![];
+    let expected = "<table>\n\
+                    <thead>\n\
+                    <tr><th style=\"text-align: left\">a</th></tr>\n\
+                    </thead>\n\
+                    <tbody>\n\
+                    </tbody>\n\
+                    </table>\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn html_color_codes() {
+    let config = AsciiTable {
+        output: Output::Html,
+        ..AsciiTable::default()
+    };
+    let input = vec![vec![format!("a{}c", "b".color(Color::Red).bg_color(Color::Blue).bold())]];
+    let expected = "<table>\n\
+                    <tbody>\n\
+                    <tr><td style=\"text-align: left\">a<span style=\"color: #cd0000; background-color: #0000ee; font-weight: bold\">b</span>c</td></tr>\n\
+                    </tbody>\n\
+                    </table>\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn csv_empty() {
+    let mut config = AsciiTable {
+        output: Output::Csv,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "a".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "b".into(), ..Column::default()});
+    config.footer = vec![vec!["x".into(), "y".into(), "z".into()]];
+    let input: Vec<Vec<i32>> = vec![];
+    let mut output = String::new();
+
+    assert_eq!("a,b,\nx,y,z\n", config.format(&input));
+    config.fmt_stream(&mut output, &input, 1).unwrap();
+    assert_eq!("a,b,\nx,y,z\n", output);
+}
+
+#[test]
+fn csv() {
+    let mut config = AsciiTable {
+        output: Output::Csv,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), max_width: 3, ..Column::default()});
+    config.columns.insert(1, Column {header: "Note".into(), ..Column::default()});
+    let input = vec![
+        vec!["plain".to_string(), "a, b".to_string()],
+        vec!["quote".to_string(), "say \"hi\"".to_string(), "extra".to_string()],
+        vec!["two\nlines".color(Color::Red).to_string()],
+    ];
+    let expected = "Name,Note,\n\
+                    plain,\"a, b\",\n\
+                    quote,\"say \"\"hi\"\"\",extra\n\
+                    \"two\nlines\",,\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn tsv() {
+    let config = AsciiTable {
+        output: Output::Tsv,
+        ..AsciiTable::default()
+    };
+    let input = vec![vec!["a", "b,c"], vec!["d\te", "f"]];
+    let expected = "a\tb,c\n\
+                    \"d\te\"\tf\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn json() {
+    let mut config = AsciiTable {
+        output: Output::Json,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    let input = vec![vec!["\"quoted\"\n", "1"], vec!["back\\slash"]];
+    let expected = "[\n\
+                    \x20 {\"Name\": \"\\\"quoted\\\"\\n\", \"col1\": \"1\"},\n\
+                    \x20 {\"Name\": \"back\\\\slash\", \"col1\": \"\"}\n\
+                    ]\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn json_empty() {
+    let config = AsciiTable {
+        output: Output::Json,
+        ..AsciiTable::default()
+    };
+    let input: Vec<Vec<i32>> = vec![];
+
+    assert_eq!("[]\n", config.format(input));
+}
+
+#[test]
+fn json_duplicate_keys() {
+    let mut config = AsciiTable {
+        output: Output::Ndjson,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "a".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "a".into(), ..Column::default()});
+    config.columns.insert(2, Column {header: "col3".into(), ..Column::default()});
+    config.columns.insert(4, Column {header: "a".into(), ..Column::default()});
+    let input = vec![[1, 2, 3, 4, 5]];
+    let expected = "{\"a\": \"1\", \"a_2\": \"2\", \"col3\": \"3\", \"col3_2\": \"4\", \"a_3\": \"5\"}\n";
+
+    assert_eq!(expected, config.format(&input));
+}
+
+#[test]
+fn ndjson() {
+    let config = AsciiTable {
+        output: Output::Ndjson,
+        ..AsciiTable::default()
+    };
+    let input = vec![vec!["a".color(Color::Red).to_string(), "\u{1}".to_string()], vec!["b".to_string()]];
+    let expected = "{\"col0\": \"a\", \"col1\": \"\\u0001\"}\n\
+                    {\"col0\": \"b\", \"col1\": \"\"}\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn latex() {
+    let mut config = AsciiTable {
+        output: Output::Latex,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(1, Column {align: Right, ..Column::default()});
+    let input = vec![vec!["50% & $5", "1"], vec!["a_b {c} ~^\\", "2"]];
+    let expected = "\\begin{tabular}{lr}\n\
+                    \\hline\n\
+                    50\\% \\& \\$5 & 1 \\\\\n\
+                    a\\_b \\{c\\} \\textasciitilde{}\\textasciicircum{}\\textbackslash{} & 2 \\\\\n\
+                    \\hline\n\
+                    \\end{tabular}\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn latex_booktabs() {
+    let mut config = AsciiTable {
+        output: Output::Booktabs,
+        header_align: Some(Center),
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), align: Center, ..Column::default()});
+    config.columns.insert(1, Column {header: "Time".into(), align: Right, ..Column::default()});
+    config.footer = vec![vec!["total".into(), "3".into()]];
+    let input = vec![vec!["two\nlines", "1"], vec!["b", "2"]];
+    let expected = "\\begin{tabular}{cr}\n\
+                    \\toprule\n\
+                    Name & \\multicolumn{1}{c}{Time} \\\\\n\
+                    \\midrule\n\
+                    \\shortstack[c]{two\\\\lines} & 1 \\\\\n\
+                    b & 2 \\\\\n\
+                    \\midrule\n\
+                    total & 3 \\\\\n\
+                    \\bottomrule\n\
+                    \\end{tabular}\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn rst_grid() {
+    let mut config = AsciiTable {
+        output: Output::RstGrid,
+        max_width: 10,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), max_width: 2, ..Column::default()});
+    config.columns.insert(1, Column {header: "Size".into(), align: Right, ..Column::default()});
+    let input = vec![vec!["a long name", "1"], vec!["two\nlines", "22"]];
+    let expected = "+-------------+------+\n\
+                    | Name        | Size |\n\
+                    +=============+======+\n\
+                    | a long name |    1 |\n\
+                    +-------------+------+\n\
+                    | two         |   22 |\n\
+                    | lines       |      |\n\
+                    +-------------+------+\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn rst_simple() {
+    let mut config = AsciiTable {
+        output: Output::RstSimple,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Description".into(), ..Column::default()});
+    let input = vec![vec!["a", "two\nlines"], vec!["", "no name"]];
+    let expected = "====  ===========\n\
+                    Name  Description\n\
+                    ====  ===========\n\
+                    a     two lines\n\
+                    ..    no name\n\
+                    ====  ===========\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn rst_simple_rule_text() {
+    let config = AsciiTable {
+        output: Output::RstSimple,
+        ..AsciiTable::default()
+    };
+    let input = vec![vec!["a", "==="], vec!["- -", "b"]];
+    let expected = "====  ====\n\
+                    a     \\===\n\
+                    \\- -  b\n\
+                    ====  ====\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn asciidoc() {
+    let mut config = AsciiTable {
+        output: Output::AsciiDoc,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Size".into(), align: Right, ..Column::default()});
+    config.columns.insert(2, Column {align: Center, ..Column::default()});
+    config.footer = vec![vec!["total".into(), "3".into()]];
+    let input = vec![vec!["a|b", "1", "x"], vec!["two\nlines", "2"]];
+    let expected = "[cols=\"<,>,^\",options=\"header,footer\"]\n\
+                    |===\n\
+                    |Name |Size |\n\
+                    |a\\|b |1 |x\n\
+                    |two +\n\
+                    lines |2 |\n\
+                    |total |3 |\n\
+                    |===\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn asciidoc_footer_rows() {
+    let mut config = AsciiTable {
+        output: Output::AsciiDoc,
+        ..AsciiTable::default()
+    };
+    config.footer = vec![vec!["sum".into(), "3".into()], vec!["avg".into(), "1.5".into()]];
+    let input = vec![[1, 2]];
+    let expected = "[cols=\"<,<\",options=\"footer\"]\n\
+                    |===\n\
+                    |1 |2\n\
+                    |sum |3\n\
+                    |avg |1.5\n\
+                    |===\n";
+
+    assert_eq!(expected, config.format(&input));
+}
+
+#[test]
+fn org() {
+    let mut config = AsciiTable {
+        output: Output::Org,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Size".into(), align: Right, max_width: 1, ..Column::default()});
+    config.footer = vec![vec!["total".into(), "333".into()]];
+    let input = vec![vec!["a|b", "1"], vec!["two\nlines", "22"]];
+    let expected = "| Name      | Size |\n\
+                    | <l>       | <r>  |\n\
+                    |-----------+------|\n\
+                    | a\\vert{}b |    1 |\n\
+                    | two lines |   22 |\n\
+                    |-----------+------|\n\
+                    | total     |  333 |\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn mediawiki() {
+    let mut config = AsciiTable {
+        output: Output::MediaWiki,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name!".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Size".into(), align: Right, ..Column::default()});
+    let input = vec![vec!["a|b", "1"], vec!["two\nlines", "22"]];
+    let expected = "{| class=\"wikitable\"\n\
+                    ! Name&#33; !! style=\"text-align: right;\" | Size\n\
+                    |-\n\
+                    | a&#124;b || style=\"text-align: right;\" | 1\n\
+                    |-\n\
+                    | two<br />lines || style=\"text-align: right;\" | 22\n\
+                    |}\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn jira() {
+    let mut config = AsciiTable {
+        output: Output::Jira,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Name".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Value".into(), ..Column::default()});
+    let input = vec![
+        vec!["a|b".to_string(), "{code}".to_string()],
+        vec!["two\nlines".to_string(), "ok".color(Color::Green).to_string()],
+        vec!["a\\".to_string(), "b\\\nc".to_string()],
+        vec!["".to_string()],
+    ];
+    let expected = "||Name||Value||\n\
+                    |a\\|b|\\{code\\}|\n\
+                    |two\\\\lines|ok|\n\
+                    |a&#92;|b&#92;\\\\c|\n\
+                    | | |\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn roff() {
+    let mut config = AsciiTable {
+        output: Output::Roff,
+        ..AsciiTable::default()
+    };
+    config.columns.insert(0, Column {header: "Option".into(), ..Column::default()});
+    config.columns.insert(1, Column {header: "Default".into(), align: Right, header_align: Some(Center), ..Column::default()});
+    config.footer = vec![vec!["_".into(), "".into()]];
+    let input = vec![vec![".hidden", "C:\\dir"], vec!["two\nlines", "1"]];
+    let expected = ".TS\n\
+                    lb cb\n\
+                    l r.\n\
+                    Option\tDefault\n\
+                    _\n\
+                    \\&.hidden\tC:\\edir\n\
+                    T{\n\
+                    two\n\
+                    .br\n\
+                    lines\n\
+                    T}\t1\n\
+                    _\n\
+                    \\&_\t\n\
+                    .TE\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn roff_text_block_markers() {
+    let config = AsciiTable {
+        output: Output::Roff,
+        ..AsciiTable::default()
+    };
+    let input = vec![vec!["T{", "a\\"], vec!["T}", "b"]];
+    let expected = ".TS\n\
+                    l l.\n\
+                    \\&T{\ta\\e\n\
+                    \\&T}\tb\n\
+                    .TE\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[test]
+fn svg() {
+    let config = AsciiTable {
+        output: Output::Svg,
+        ..AsciiTable::default()
+    };
+    let input = vec![vec!["a<b".to_string(), "ok".color(Color::Red).bg_color(Color::Blue).bold().to_string()]];
+    let expected = "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"108\" height=\"54\" font-family=\"monospace\" font-size=\"15\">\n\
+                    <text y=\"15\" xml:space=\"preserve\"><tspan x=\"0\">┌─────┬────┐</tspan></text>\n\
+                    <rect x=\"72\" y=\"18\" width=\"18\" height=\"18\" fill=\"#0000ee\"/>\n\
+                    <text y=\"33\" xml:space=\"preserve\"><tspan x=\"0\">│ a&lt;b │ </tspan><tspan x=\"72\" fill=\"#cd0000\" font-weight=\"bold\">ok</tspan><tspan x=\"90\"> │</tspan></text>\n\
+                    <text y=\"51\" xml:space=\"preserve\"><tspan x=\"0\">└─────┴────┘</tspan></text>\n\
+                    </svg>\n";
+
+    assert_eq!(expected, config.format(input));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn records() {
+    #[derive(serde::Serialize)]
+    struct Address {
+        city: &'static str,
+        zip: Option<u32>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Person {
+        name: &'static str,
+        age: u8,
+        address: Address,
+        tags: Vec<&'static str>,
+    }
+
+    let mut config = AsciiTable::default();
+    config.columns.insert(1, Column {header: "Age".into(), align: Right, ..Column::default()});
+    let input = [
+        Person {name: "Ann", age: 31, address: Address {city: "Oslo", zip: Some(150)}, tags: vec!["a", "b"]},
+        Person {name: "Bob", age: 7, address: Address {city: "Rome", zip: None}, tags: vec![]},
+    ];
+    let expected = "┌──────┬─────┬──────────────┬─────────────┬────────┐\n\
+                    │ name │ Age │ address.city │ address.zip │ tags   │\n\
+                    ├──────┼─────┼──────────────┼─────────────┼────────┤\n\
+                    │ Ann  │  31 │ Oslo         │ 150         │ [a, b] │\n\
+                    │ Bob  │   7 │ Rome         │             │ []     │\n\
+                    └──────┴─────┴──────────────┴─────────────┴────────┘\n";
+
+    assert_eq!(expected, config.format_records(&input).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn records_tuples() {
+    let config = AsciiTable::default();
+    let input = [(1, "a"), (2, "b")];
+    let expected = "┌───┬───┐\n\
+                    │ 0 │ 1 │\n\
+                    ├───┼───┤\n\
+                    │ 1 │ a │\n\
+                    │ 2 │ b │\n\
+                    └───┴───┘\n";
+
+    assert_eq!(expected, config.format_records(&input).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn records_optional_struct() {
+    #[derive(serde::Serialize)]
+    struct Owner {
+        name: &'static str,
+        city: &'static str,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Item {
+        id: u32,
+        owner: Option<Owner>,
+    }
+
+    let config = AsciiTable::default();
+    let input = [
+        Item {id: 1, owner: Some(Owner {name: "Ann", city: "Oslo"})},
+        Item {id: 2, owner: None},
+        Item {id: 3, owner: Some(Owner {name: "Bob", city: "Rome"})},
+    ];
+    let expected = "┌────┬────────────┬────────────┐\n\
+                    │ id │ owner.name │ owner.city │\n\
+                    ├────┼────────────┼────────────┤\n\
+                    │ 1  │ Ann        │ Oslo       │\n\
+                    │ 2  │            │            │\n\
+                    │ 3  │ Bob        │ Rome       │\n\
+                    └────┴────────────┴────────────┘\n";
+
+    assert_eq!(expected, config.format_records(&input).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn records_first_none() {
+    #[derive(serde::Serialize)]
+    struct Owner {
+        name: &'static str,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Item {
+        id: u32,
+        score: Option<u32>,
+        owner: Option<Owner>,
+        note: &'static str,
+    }
+
+    let mut config = AsciiTable::default();
+    config.columns.insert(1, Column {header: "Score".into(), align: Right, ..Column::default()});
+    let input = [
+        Item {id: 1, score: None, owner: None, note: "x"},
+        Item {id: 2, score: Some(50), owner: Some(Owner {name: "Ann"}), note: "y"},
+    ];
+    let expected = "┌────┬───────┬────────────┬──────┐\n\
+                    │ id │ Score │ owner.name │ note │\n\
+                    ├────┼───────┼────────────┼──────┤\n\
+                    │ 1  │       │            │ x    │\n\
+                    │ 2  │    50 │ Ann        │ y    │\n\
+                    └────┴───────┴────────────┴──────┘\n";
+
+    assert_eq!(expected, config.format_records(&input).unwrap());
+
+    let input = [Item {id: 1, score: None, owner: None, note: "x"}];
+    let expected = "┌────┬───────┬───────┬──────┐\n\
+                    │ id │ Score │ owner │ note │\n\
+                    ├────┼───────┼───────┼──────┤\n\
+                    │ 1  │       │       │ x    │\n\
+                    └────┴───────┴───────┴──────┘\n";
+
+    assert_eq!(expected, config.format_records(&input).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn records_error() {
+    struct Broken;
+
+    impl serde::Serialize for Broken {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("broken record"))
+        }
+    }
+
+    let config = AsciiTable::default();
+    let error = config.format_records(&[Broken]).unwrap_err();
+    let mut output = Vec::new();
+
+    assert_eq!("broken record", error.to_string());
+    assert!(config.print_records(&[Broken]).is_err());
+    let error = config.write_records(&mut output, &[Broken]).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+    assert!(output.is_empty());
+}